@@ -0,0 +1,97 @@
+use chrono::{Datelike, NaiveDate};
+
+/// The calendar unit an [AnchoredStep] counts in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepUnit {
+    /// Whole days.
+    Days,
+    /// Seven day blocks, starting on the anchor date.
+    Weeks,
+    /// Calendar months, regardless of their length.
+    Months,
+}
+
+/// A step that counts real calendar distance from an anchor date.
+///
+/// A period like `1/10` in the days of month field restarts at the beginning of every month.
+/// An `AnchoredStep` keeps counting across month and year boundaries instead, which makes
+/// schedules like "every 45 days" or "every other week on Tuesday" expressible. Dates before
+/// the anchor are stepped backwards from it.
+/// # Example
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use cron::{AnchoredStep, Schedule};
+/// use std::str::FromStr;
+///
+/// let anchor = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(); // A Tuesday
+/// let schedule = Schedule::from_str("0 0 9 ? * Tue")
+///     .unwrap()
+///     .with_anchored_step(AnchoredStep::weeks(anchor, 2));
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let mut every_other_tuesday = schedule.after(&start);
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).unwrap(), every_other_tuesday.next().unwrap());
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 16, 9, 0, 0).unwrap(), every_other_tuesday.next().unwrap());
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 30, 9, 0, 0).unwrap(), every_other_tuesday.next().unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AnchoredStep {
+    anchor: NaiveDate,
+    unit: StepUnit,
+    step: u32,
+}
+
+impl AnchoredStep {
+    /// Creates a step of `step` units counted from `anchor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn new(anchor: NaiveDate, unit: StepUnit, step: u32) -> AnchoredStep {
+        assert!(step != 0, "anchored step cannot be zero");
+        AnchoredStep { anchor, unit, step }
+    }
+
+    /// Matches every `step`th day counted from `anchor`.
+    pub fn days(anchor: NaiveDate, step: u32) -> AnchoredStep {
+        Self::new(anchor, StepUnit::Days, step)
+    }
+
+    /// Matches every day in every `step`th week counted from `anchor`.
+    pub fn weeks(anchor: NaiveDate, step: u32) -> AnchoredStep {
+        Self::new(anchor, StepUnit::Weeks, step)
+    }
+
+    /// Matches every day in every `step`th month counted from the month containing `anchor`.
+    pub fn months(anchor: NaiveDate, step: u32) -> AnchoredStep {
+        Self::new(anchor, StepUnit::Months, step)
+    }
+
+    /// Returns the date the step counts from.
+    pub fn anchor(&self) -> NaiveDate {
+        self.anchor
+    }
+
+    /// Returns the unit the step counts in.
+    pub fn unit(&self) -> StepUnit {
+        self.unit
+    }
+
+    /// Returns the number of units between matches.
+    pub fn step(&self) -> u32 {
+        self.step
+    }
+
+    /// Returns true if `date` falls on one of the steps.
+    pub fn includes(&self, date: NaiveDate) -> bool {
+        let distance = match self.unit {
+            StepUnit::Days => (date - self.anchor).num_days(),
+            StepUnit::Weeks => (date - self.anchor).num_days().div_euclid(7),
+            StepUnit::Months => {
+                let months = |d: NaiveDate| d.year() as i64 * 12 + d.month0() as i64;
+                months(date) - months(self.anchor)
+            }
+        };
+        distance.rem_euclid(self.step as i64) == 0
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::offset::{LocalResult, Offset, TimeZone};
+use chrono::{DateTime, Duration, NaiveDateTime};
+
+use super::{ScheduleDateTime, ScheduleTimeZone};
+
+impl<Z> ScheduleTimeZone for Z
+where
+    Z: TimeZone,
+{
+    type DateTime = DateTime<Z>;
+
+    fn at_local(&self, local: &NaiveDateTime) -> LocalResult<DateTime<Z>> {
+        self.from_local_datetime(local)
+    }
+
+    fn at_utc(&self, utc: &NaiveDateTime) -> Option<DateTime<Z>> {
+        Some(self.from_utc_datetime(utc))
+    }
+}
+
+impl<Z> ScheduleDateTime for DateTime<Z>
+where
+    Z: TimeZone,
+{
+    type TimeZone = Z;
+
+    fn time_zone(&self) -> Z {
+        self.timezone()
+    }
+
+    fn naive_local(&self) -> NaiveDateTime {
+        DateTime::naive_local(self)
+    }
+
+    fn with_time_zone(&self, time_zone: &Z) -> DateTime<Z> {
+        self.with_timezone(time_zone)
+    }
+
+    fn utc_offset_seconds(&self) -> i32 {
+        self.offset().fix().local_minus_utc()
+    }
+
+    fn checked_add_signed(&self, duration: Duration) -> Option<DateTime<Z>> {
+        DateTime::checked_add_signed(self.clone(), duration)
+    }
+}
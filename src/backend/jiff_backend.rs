@@ -0,0 +1,119 @@
+use chrono::{Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, Timelike};
+use jiff::civil;
+use jiff::tz::{AmbiguousOffset, Disambiguation, Offset, TimeZone};
+use jiff::{SignedDuration, Zoned};
+
+use super::{ScheduleDateTime, ScheduleTimeZone};
+
+/// The time zone of a `jiff::Zoned`.
+///
+/// Wall clock times in a DST gap have no instants, and times in a DST fold resolve to both
+/// [Disambiguation::Earlier] and [Disambiguation::Later], so schedules fire the same way they do
+/// with a chrono time zone.
+/// # Example
+/// ```
+/// use cron::{JiffZone, Schedule};
+/// use jiff::civil::date;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 30 1 * * ?").unwrap();
+/// let after = date(2024, 11, 3).in_tz("America/New_York").unwrap();
+/// let mut fires = schedule.after(&after);
+/// assert_eq!("2024-11-03T01:30:00-04:00[America/New_York]", fires.next().unwrap().to_string());
+/// assert_eq!("2024-11-03T01:30:00-05:00[America/New_York]", fires.next().unwrap().to_string());
+///
+/// let mut upcoming = schedule.upcoming(JiffZone::from(jiff::tz::TimeZone::UTC));
+/// assert!(upcoming.next().is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JiffZone(pub TimeZone);
+
+impl From<TimeZone> for JiffZone {
+    fn from(time_zone: TimeZone) -> JiffZone {
+        JiffZone(time_zone)
+    }
+}
+
+impl ScheduleTimeZone for JiffZone {
+    type DateTime = Zoned;
+
+    fn at_local(&self, local: &NaiveDateTime) -> LocalResult<Zoned> {
+        let Some(local) = to_civil(local) else {
+            return LocalResult::None;
+        };
+        let ambiguous = self.0.to_ambiguous_zoned(local);
+        match ambiguous.offset() {
+            AmbiguousOffset::Unambiguous { .. } => ambiguous
+                .disambiguate(Disambiguation::Reject)
+                .map_or(LocalResult::None, LocalResult::Single),
+            AmbiguousOffset::Gap { .. } => LocalResult::None,
+            AmbiguousOffset::Fold { .. } => {
+                let earlier = ambiguous.clone().disambiguate(Disambiguation::Earlier);
+                let later = ambiguous.disambiguate(Disambiguation::Later);
+                match (earlier, later) {
+                    (Ok(earlier), Ok(later)) => LocalResult::Ambiguous(earlier, later),
+                    (Ok(single), Err(_)) | (Err(_), Ok(single)) => LocalResult::Single(single),
+                    (Err(_), Err(_)) => LocalResult::None,
+                }
+            }
+        }
+    }
+
+    fn at_utc(&self, utc: &NaiveDateTime) -> Option<Zoned> {
+        let utc = Offset::UTC.to_timestamp(to_civil(utc)?).ok()?;
+        Some(utc.to_zoned(self.0.clone()))
+    }
+}
+
+impl ScheduleDateTime for Zoned {
+    type TimeZone = JiffZone;
+
+    fn time_zone(&self) -> JiffZone {
+        JiffZone(Zoned::time_zone(self).clone())
+    }
+
+    fn naive_local(&self) -> NaiveDateTime {
+        let local = self.datetime();
+        NaiveDate::from_ymd_opt(
+            i32::from(local.year()),
+            local.month() as u32,
+            local.day() as u32,
+        )
+        .and_then(|date| {
+            date.and_hms_nano_opt(
+                local.hour() as u32,
+                local.minute() as u32,
+                local.second() as u32,
+                local.subsec_nanosecond() as u32,
+            )
+        })
+        .expect("every date supported by jiff is supported by chrono")
+    }
+
+    fn with_time_zone(&self, time_zone: &JiffZone) -> Zoned {
+        Zoned::with_time_zone(self, time_zone.0.clone())
+    }
+
+    fn utc_offset_seconds(&self) -> i32 {
+        self.offset().seconds()
+    }
+
+    fn checked_add_signed(&self, duration: Duration) -> Option<Zoned> {
+        let duration = SignedDuration::new(duration.num_seconds(), duration.subsec_nanos());
+        self.checked_add(duration).ok()
+    }
+}
+
+/// Converts a chrono wall clock time, or returns `None` if it's out of jiff's range.
+fn to_civil(local: &NaiveDateTime) -> Option<civil::DateTime> {
+    civil::DateTime::new(
+        i16::try_from(local.year()).ok()?,
+        local.month() as i8,
+        local.day() as i8,
+        local.hour() as i8,
+        local.minute() as i8,
+        i8::try_from(local.second()).ok()?,
+        i32::try_from(local.nanosecond()).ok()?,
+    )
+    .ok()
+}
@@ -0,0 +1,57 @@
+mod chrono_backend;
+#[cfg(feature = "jiff")]
+mod jiff_backend;
+mod naive_backend;
+#[cfg(feature = "time")]
+mod time_backend;
+
+#[cfg(feature = "jiff")]
+pub use self::jiff_backend::JiffZone;
+pub use self::naive_backend::NaiveZone;
+#[cfg(feature = "time")]
+pub use self::time_backend::OffsetZone;
+
+use chrono::{Duration, LocalResult, NaiveDateTime};
+
+/// A time zone that a [Schedule](crate::Schedule) can search for matching DateTimes in.
+///
+/// This is implemented for every chrono [TimeZone](chrono::TimeZone) and for [NaiveZone]. Other
+/// date and time libraries can be supported by implementing it together with [ScheduleDateTime].
+pub trait ScheduleTimeZone: Clone {
+    /// The DateTime type that belongs to this time zone.
+    type DateTime: ScheduleDateTime<TimeZone = Self>;
+
+    /// Finds the instants with the given wall clock time in this time zone. There are none in
+    /// a DST gap and two in a DST fold.
+    fn at_local(&self, local: &NaiveDateTime) -> LocalResult<Self::DateTime>;
+
+    /// Finds the instant with the given wall clock time in UTC, or `None` if it's outside of the
+    /// range this time zone's DateTimes can represent.
+    fn at_utc(&self, utc: &NaiveDateTime) -> Option<Self::DateTime>;
+}
+
+/// An instant in a [ScheduleTimeZone], as used and returned by [Schedule](crate::Schedule).
+///
+/// DateTimes are compared as instants, regardless of their time zones. None of the methods may
+/// panic: since schedules never match years after 2100, instants that can't be converted
+/// exactly are clamped to the nearest end of the range instead.
+pub trait ScheduleDateTime: Clone + Ord {
+    /// The time zone this DateTime is in.
+    type TimeZone: ScheduleTimeZone<DateTime = Self>;
+
+    /// Returns the time zone this DateTime is in.
+    fn time_zone(&self) -> Self::TimeZone;
+
+    /// Returns the wall clock time of this DateTime in its time zone, clamped to chrono's range.
+    fn naive_local(&self) -> NaiveDateTime;
+
+    /// Returns the same instant in another instance of the time zone type, or the nearest end
+    /// of the range that instance can represent.
+    fn with_time_zone(&self, time_zone: &Self::TimeZone) -> Self;
+
+    /// Returns the difference between local time and UTC in seconds.
+    fn utc_offset_seconds(&self) -> i32;
+
+    /// Adds a duration, returning `None` if the result can't be represented.
+    fn checked_add_signed(&self, duration: Duration) -> Option<Self>;
+}
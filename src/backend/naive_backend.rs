@@ -0,0 +1,47 @@
+use chrono::{Duration, LocalResult, NaiveDateTime};
+
+use super::{ScheduleDateTime, ScheduleTimeZone};
+
+/// The time zone of a `NaiveDateTime`: wall clock time with no UTC offset or DST transitions.
+///
+/// Every wall clock time exists exactly once, so schedules fire at every matching time. The
+/// current time is read as UTC by
+/// [Schedule::upcoming_with_clock](crate::Schedule::upcoming_with_clock).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NaiveZone;
+
+impl ScheduleTimeZone for NaiveZone {
+    type DateTime = NaiveDateTime;
+
+    fn at_local(&self, local: &NaiveDateTime) -> LocalResult<NaiveDateTime> {
+        LocalResult::Single(*local)
+    }
+
+    fn at_utc(&self, utc: &NaiveDateTime) -> Option<NaiveDateTime> {
+        Some(*utc)
+    }
+}
+
+impl ScheduleDateTime for NaiveDateTime {
+    type TimeZone = NaiveZone;
+
+    fn time_zone(&self) -> NaiveZone {
+        NaiveZone
+    }
+
+    fn naive_local(&self) -> NaiveDateTime {
+        *self
+    }
+
+    fn with_time_zone(&self, _time_zone: &NaiveZone) -> NaiveDateTime {
+        *self
+    }
+
+    fn utc_offset_seconds(&self) -> i32 {
+        0
+    }
+
+    fn checked_add_signed(&self, duration: Duration) -> Option<NaiveDateTime> {
+        NaiveDateTime::checked_add_signed(*self, duration)
+    }
+}
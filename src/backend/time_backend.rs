@@ -0,0 +1,111 @@
+use chrono::{Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, Timelike};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::{ScheduleDateTime, ScheduleTimeZone};
+
+/// The time zone of a `time::OffsetDateTime`, which is a fixed offset from UTC.
+///
+/// Schedules are searched in this offset, so there are no DST transitions to account for.
+/// # Example
+/// ```
+/// use cron::{OffsetZone, Schedule};
+/// use std::str::FromStr;
+/// use time::macros::datetime;
+///
+/// let schedule = Schedule::from_str("0 0 9 * * ?").unwrap();
+/// let after = datetime!(2024-01-01 12:00 +2);
+/// assert_eq!(Some(datetime!(2024-01-02 9:00 +2)), schedule.next_after(&after));
+///
+/// let mut upcoming = schedule.upcoming(OffsetZone::UTC);
+/// assert!(upcoming.next().is_some());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OffsetZone(pub UtcOffset);
+
+impl OffsetZone {
+    /// The zone for UTC itself.
+    pub const UTC: OffsetZone = OffsetZone(UtcOffset::UTC);
+}
+
+impl From<UtcOffset> for OffsetZone {
+    fn from(offset: UtcOffset) -> OffsetZone {
+        OffsetZone(offset)
+    }
+}
+
+impl ScheduleTimeZone for OffsetZone {
+    type DateTime = OffsetDateTime;
+
+    fn at_local(&self, local: &NaiveDateTime) -> LocalResult<OffsetDateTime> {
+        match to_primitive(local) {
+            Some(local) => LocalResult::Single(local.assume_offset(self.0)),
+            None => LocalResult::None,
+        }
+    }
+
+    fn at_utc(&self, utc: &NaiveDateTime) -> Option<OffsetDateTime> {
+        to_primitive(utc)?.assume_utc().checked_to_offset(self.0)
+    }
+}
+
+impl ScheduleDateTime for OffsetDateTime {
+    type TimeZone = OffsetZone;
+
+    fn time_zone(&self) -> OffsetZone {
+        OffsetZone(self.offset())
+    }
+
+    fn naive_local(&self) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(self.year(), self.month() as u32, self.day() as u32)
+            .and_then(|date| {
+                date.and_hms_nano_opt(
+                    self.hour() as u32,
+                    self.minute() as u32,
+                    self.second() as u32,
+                    self.nanosecond(),
+                )
+            })
+            // With the time crate's `large-dates` feature, dates can be out of chrono's range.
+            .unwrap_or(if self.year() < 0 {
+                NaiveDateTime::MIN
+            } else {
+                NaiveDateTime::MAX
+            })
+    }
+
+    fn with_time_zone(&self, time_zone: &OffsetZone) -> OffsetDateTime {
+        // Near the ends of the time crate's range, the instant may have no wall clock time in
+        // the other offset.
+        self.checked_to_offset(time_zone.0).unwrap_or_else(|| {
+            let end = if self.year() < 0 {
+                Date::MIN.midnight()
+            } else {
+                Date::MAX.with_time(Time::MAX)
+            };
+            end.assume_offset(time_zone.0)
+        })
+    }
+
+    fn utc_offset_seconds(&self) -> i32 {
+        self.offset().whole_seconds()
+    }
+
+    fn checked_add_signed(&self, duration: Duration) -> Option<OffsetDateTime> {
+        let duration = time::Duration::new(duration.num_seconds(), duration.subsec_nanos());
+        self.checked_add(duration)
+    }
+}
+
+/// Converts a chrono wall clock time, or returns `None` if it's out of the time crate's range.
+fn to_primitive(local: &NaiveDateTime) -> Option<PrimitiveDateTime> {
+    let month = Month::try_from(local.month() as u8).ok()?;
+    let date = Date::from_calendar_date(local.year(), month, local.day() as u8).ok()?;
+    let time = Time::from_hms_nano(
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+        local.nanosecond(),
+    )
+    .ok()?;
+    Some(PrimitiveDateTime::new(date, time))
+}
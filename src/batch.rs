@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread;
+
+use crate::backend::ScheduleDateTime;
+use crate::compiled::{CompiledSchedule, DayMasks};
+use crate::schedule::Schedule;
+
+/// Compiling covers every supported month, so it only pays off once enough schedules with the
+/// same date fields share the result.
+const MIN_SCHEDULES_PER_COMPILE: usize = 32;
+
+/// Returns the first DateTime after `after` for each of the `schedules`, in the same order.
+///
+/// Identical schedules are only evaluated once, and schedules that share their date fields
+/// share one set of precompiled days, as in [CompiledSchedule](crate::CompiledSchedule).
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{next_after_each, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedules = vec![
+///     Schedule::from_str("0 0 9 * * ?").unwrap(),
+///     Schedule::from_str("0 30 8 * * ?").unwrap(),
+///     Schedule::from_str("0 0 0 1 1 ? 2020").unwrap(),
+/// ];
+/// let after = Utc.with_ymd_and_hms(2024, 1, 1, 8, 45, 0).unwrap();
+/// assert_eq!(
+///     vec![
+///         Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).single(),
+///         Utc.with_ymd_and_hms(2024, 1, 2, 8, 30, 0).single(),
+///         None,
+///     ],
+///     next_after_each(&schedules, &after)
+/// );
+/// ```
+pub fn next_after_each<D>(schedules: &[Schedule], after: &D) -> Vec<Option<D>>
+where
+    D: ScheduleDateTime,
+{
+    let batch = Batch::new(schedules);
+    let distinct = batch
+        .distinct
+        .iter()
+        .map(|schedule| schedule.next_after(after))
+        .collect();
+    batch.spread(distinct)
+}
+
+/// Does the same as [next_after_each], splitting the work across up to `threads` threads.
+pub fn next_after_each_parallel<D>(
+    schedules: &[Schedule],
+    after: &D,
+    threads: NonZeroUsize,
+) -> Vec<Option<D>>
+where
+    D: ScheduleDateTime + Send + Sync,
+{
+    let batch = Batch::new(schedules);
+    let chunk_size = batch.distinct.len().div_ceil(threads.get()).max(1);
+    let distinct = thread::scope(|scope| {
+        let handles = batch
+            .distinct
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|schedule| schedule.next_after(after))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    batch.spread(distinct)
+}
+
+struct Batch {
+    /// Each distinct schedule once, with shared day masks where they're worth compiling.
+    distinct: Vec<Distinct>,
+    /// The index in `distinct` of each of the schedules passed in.
+    positions: Vec<usize>,
+}
+
+impl Batch {
+    fn new(schedules: &[Schedule]) -> Batch {
+        let mut by_source: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut unique: Vec<&Schedule> = Vec::new();
+        let positions = schedules
+            .iter()
+            .map(|schedule| {
+                let candidates = by_source.entry(schedule.source()).or_default();
+                match candidates.iter().find(|&&i| unique[i] == schedule) {
+                    Some(&i) => i,
+                    None => {
+                        candidates.push(unique.len());
+                        unique.push(schedule);
+                        unique.len() - 1
+                    }
+                }
+            })
+            .collect();
+
+        let mut by_dates: HashMap<_, Vec<usize>> = HashMap::new();
+        for (i, schedule) in unique.iter().enumerate() {
+            by_dates.entry(schedule.date_key()).or_default().push(i);
+        }
+        let mut distinct: Vec<Option<Distinct>> = (0..unique.len()).map(|_| None).collect();
+        for group in by_dates.values() {
+            if group.len() < MIN_SCHEDULES_PER_COMPILE {
+                for &i in group {
+                    distinct[i] = Some(Distinct::Schedule(unique[i].clone()));
+                }
+                continue;
+            }
+            let day_masks = DayMasks::compile(unique[group[0]]);
+            for &i in group {
+                let compiled =
+                    CompiledSchedule::with_day_masks(unique[i].clone(), day_masks.clone());
+                distinct[i] = Some(Distinct::Compiled(compiled));
+            }
+        }
+
+        Batch {
+            distinct: distinct.into_iter().flatten().collect(),
+            positions,
+        }
+    }
+
+    fn spread<T: Clone>(&self, distinct: Vec<T>) -> Vec<T> {
+        self.positions
+            .iter()
+            .map(|&i| distinct[i].clone())
+            .collect()
+    }
+}
+
+/// A distinct schedule in a [Batch], compiled if enough schedules share its date fields.
+enum Distinct {
+    Schedule(Schedule),
+    Compiled(CompiledSchedule),
+}
+
+impl Distinct {
+    fn next_after<D>(&self, after: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        match self {
+            Distinct::Schedule(schedule) => schedule.next_after(after),
+            Distinct::Compiled(compiled) => compiled.next_after(after),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Duration, Utc};
+#[cfg(not(feature = "std"))]
+use core::cell::Cell;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+
+/// A source of the current time, for the methods that start from the present.
+///
+/// With the `std` feature, `Schedule::upcoming` reads the `SystemClock`. The
+/// [upcoming_with_clock](crate::Schedule::upcoming_with_clock) variants take any other clock, like
+/// a [MockClock] in tests.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<C> Clock for &C
+where
+    C: Clock + ?Sized,
+{
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// The system's clock, as read by [Utc::now].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when it's told to.
+///
+/// With the `std` feature the clock is `Sync`, so threads under test can share one clock and
+/// move it while others read it.
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron::{MockClock, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 * * * ?").unwrap();
+/// let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 9, 30, 0).unwrap());
+/// let next = schedule.upcoming_with_clock(Utc, &clock).next();
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).single(), next);
+///
+/// clock.advance(Duration::hours(1));
+/// let next = schedule.upcoming_with_clock(Utc, &clock).next();
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 11, 0, 0).single(), next);
+/// ```
+pub struct MockClock {
+    #[cfg(feature = "std")]
+    now: Mutex<DateTime<Utc>>,
+    #[cfg(not(feature = "std"))]
+    now: Cell<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: DateTime<Utc>) -> MockClock {
+        MockClock {
+            #[cfg(feature = "std")]
+            now: Mutex::new(now),
+            #[cfg(not(feature = "std"))]
+            now: Cell::new(now),
+        }
+    }
+
+    /// Moves the clock to `now`, which may be in the past.
+    pub fn set(&self, now: DateTime<Utc>) {
+        self.update(|_| now);
+    }
+
+    /// Moves the clock by `duration`, which may be negative.
+    pub fn advance(&self, duration: Duration) {
+        self.update(|now| now + duration);
+    }
+
+    #[cfg(feature = "std")]
+    fn update(&self, f: impl FnOnce(DateTime<Utc>) -> DateTime<Utc>) {
+        // The time is always valid, even if a thread panicked while holding the lock.
+        let mut now = self.now.lock().unwrap_or_else(PoisonError::into_inner);
+        *now = f(*now);
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn update(&self, f: impl FnOnce(DateTime<Utc>) -> DateTime<Utc>) {
+        self.now.set(f(self.now.get()));
+    }
+}
+
+impl Clock for MockClock {
+    #[cfg(feature = "std")]
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+}
+
+impl Clone for MockClock {
+    fn clone(&self) -> MockClock {
+        MockClock::new(self.now())
+    }
+}
+
+impl PartialEq for MockClock {
+    fn eq(&self, other: &MockClock) -> bool {
+        self.now() == other.now()
+    }
+}
+
+impl Eq for MockClock {}
+
+impl fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
@@ -0,0 +1,419 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use chrono::{NaiveDate, NaiveDateTime};
+use core::fmt;
+use core::iter::Rev;
+use core::ops::Bound::Unbounded;
+use core::ops::{Deref, RangeBounds};
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::clock::Clock;
+#[cfg(feature = "std")]
+use crate::clock::SystemClock;
+use crate::dst_anomaly::DstAnomaly;
+use crate::ordinal::Ordinal;
+use crate::schedule::{
+    Matcher, NaiveScheduleIterator, OwnedScheduleIterator, Schedule, ScheduleIterator,
+    ScheduleRangeIterator,
+};
+use crate::time_unit::{TimeUnitField, TimeUnitSpec, Years};
+
+/// A [Schedule] prepared for repeated evaluation.
+///
+/// Compiling works out which days match the date fields for every month the schedule can fire
+/// in, and which hours, minutes and seconds match, so queries no longer intersect the days of
+/// week and days of month fields, build dates or check the optional date filters on every call.
+/// The query and iterator methods use these tables, and the rest of the [Schedule] methods are
+/// available through `Deref`. Changing the schedule means taking it out with
+/// [CompiledSchedule::into_schedule] and compiling it again.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{CompiledSchedule, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 9 ? * Mon-Fri").unwrap();
+/// let compiled = CompiledSchedule::new(schedule);
+///
+/// let friday = Utc.with_ymd_and_hms(2024, 5, 3, 12, 0, 0).unwrap();
+/// assert_eq!(
+///     Utc.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).single(),
+///     compiled.next_after(&friday)
+/// );
+/// assert!(compiled.includes(Utc.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).unwrap()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompiledSchedule {
+    schedule: Schedule,
+    tables: Arc<Tables>,
+}
+
+impl CompiledSchedule {
+    /// Compiles `schedule`.
+    pub fn new(schedule: Schedule) -> CompiledSchedule {
+        let tables = Tables::new(&schedule, DayMasks::compile(&schedule));
+        CompiledSchedule {
+            schedule,
+            tables: Arc::new(tables),
+        }
+    }
+
+    /// Compiles `schedule` reusing the day masks of a schedule with the same date fields.
+    #[cfg(feature = "std")]
+    pub(crate) fn with_day_masks(schedule: Schedule, day_masks: DayMasks) -> CompiledSchedule {
+        let tables = Tables::new(&schedule, day_masks);
+        CompiledSchedule {
+            schedule,
+            tables: Arc::new(tables),
+        }
+    }
+
+    /// Returns the compiled [Schedule].
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the [Schedule] this was compiled from.
+    pub fn into_schedule(self) -> Schedule {
+        self.schedule
+    }
+
+    /// See [Schedule::next_after].
+    pub fn next_after<D>(&self, after: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().next_within(after, Unbounded)
+    }
+
+    /// See [Schedule::next_at_or_after].
+    pub fn next_at_or_after<D>(&self, date_time: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().next_at_or_after(date_time)
+    }
+
+    /// See [Schedule::prev_before].
+    pub fn prev_before<D>(&self, before: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().prev_within(before, Unbounded)
+    }
+
+    /// See [Schedule::prev_at_or_before].
+    pub fn prev_at_or_before<D>(&self, date_time: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().prev_at_or_before(date_time)
+    }
+
+    /// See [Schedule::upcoming].
+    #[cfg(feature = "std")]
+    pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+    {
+        self.upcoming_with_clock(timezone, &SystemClock)
+    }
+
+    /// See [Schedule::upcoming_owned].
+    #[cfg(feature = "std")]
+    pub fn upcoming_owned<Z: ScheduleTimeZone>(&self, timezone: Z) -> OwnedScheduleIterator<Z> {
+        self.upcoming_owned_with_clock(timezone, &SystemClock)
+    }
+
+    /// See [Schedule::upcoming_with_clock].
+    pub fn upcoming_with_clock<Z, C>(&self, timezone: Z, clock: &C) -> ScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        ScheduleIterator::new(self.matcher(), timezone.at_utc(&clock.now().naive_utc()))
+    }
+
+    /// See [Schedule::upcoming_owned_with_clock].
+    pub fn upcoming_owned_with_clock<Z, C>(
+        &self,
+        timezone: Z,
+        clock: &C,
+    ) -> OwnedScheduleIterator<Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        OwnedScheduleIterator::starting_at(
+            self.schedule.clone(),
+            Some(self.tables.clone()),
+            timezone.at_utc(&clock.now().naive_utc()),
+        )
+    }
+
+    /// See [Schedule::after].
+    pub fn after<D>(&self, after: &D) -> ScheduleIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+    {
+        ScheduleIterator::new(self.matcher(), Some(after.clone()))
+    }
+
+    /// See [Schedule::after_naive].
+    pub fn after_naive(&self, after: &NaiveDateTime) -> NaiveScheduleIterator<'_> {
+        self.after(after)
+    }
+
+    /// See [Schedule::after_owned].
+    pub fn after_owned<D: ScheduleDateTime>(&self, after: D) -> OwnedScheduleIterator<D::TimeZone> {
+        OwnedScheduleIterator::starting_at(
+            self.schedule.clone(),
+            Some(self.tables.clone()),
+            Some(after),
+        )
+    }
+
+    /// See [Schedule::before].
+    pub fn before<D>(&self, before: &D) -> Rev<ScheduleIterator<'_, D::TimeZone>>
+    where
+        D: ScheduleDateTime,
+    {
+        self.after(before).rev()
+    }
+
+    /// See [Schedule::before_owned].
+    pub fn before_owned<D: ScheduleDateTime>(
+        &self,
+        before: D,
+    ) -> Rev<OwnedScheduleIterator<D::TimeZone>> {
+        self.after_owned(before).rev()
+    }
+
+    /// See [Schedule::between].
+    pub fn between<D, R>(&self, range: R) -> ScheduleRangeIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        ScheduleRangeIterator::new(
+            self.matcher(),
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        )
+    }
+
+    /// See [Schedule::count_between].
+    pub fn count_between<D, R>(&self, range: R) -> u64
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        self.matcher().count_between(range)
+    }
+
+    /// See [Schedule::nth_after].
+    pub fn nth_after<D>(&self, after: &D, n: u64) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().nth_after(after, n)
+    }
+
+    /// See [Schedule::dst_anomalies].
+    pub fn dst_anomalies<Z, R>(&self, timezone: &Z, dates: R) -> Vec<DstAnomaly<Z::DateTime>>
+    where
+        Z: ScheduleTimeZone,
+        R: RangeBounds<NaiveDate>,
+    {
+        self.matcher().dst_anomalies(timezone, dates)
+    }
+
+    /// See [Schedule::includes].
+    pub fn includes<D>(&self, date_time: D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().includes(date_time)
+    }
+
+    /// See [Schedule::includes_naive].
+    pub fn includes_naive(&self, local: NaiveDateTime) -> bool {
+        self.matcher().includes_naive(local)
+    }
+
+    pub(crate) fn matcher(&self) -> Matcher<'_> {
+        Matcher::new(&self.schedule, Some(&self.tables))
+    }
+}
+
+impl From<Schedule> for CompiledSchedule {
+    fn from(schedule: Schedule) -> CompiledSchedule {
+        CompiledSchedule::new(schedule)
+    }
+}
+
+impl Deref for CompiledSchedule {
+    type Target = Schedule;
+
+    fn deref(&self) -> &Schedule {
+        &self.schedule
+    }
+}
+
+// The tables are worked out from the schedule, so comparing the schedules is enough.
+impl PartialEq for CompiledSchedule {
+    fn eq(&self, other: &CompiledSchedule) -> bool {
+        self.schedule == other.schedule
+    }
+}
+
+impl Eq for CompiledSchedule {}
+
+/// Lookup tables for each of a schedule's fields.
+pub(crate) struct Tables {
+    day_masks: DayMasks,
+    hours: TimeTable,
+    minutes: TimeTable,
+    seconds: TimeTable,
+    per_day: u64,
+}
+
+impl Tables {
+    fn new(schedule: &Schedule, day_masks: DayMasks) -> Tables {
+        let hours = TimeTable::new(schedule.hours());
+        let minutes = TimeTable::new(schedule.minutes());
+        let seconds = TimeTable::new(schedule.seconds());
+        Tables {
+            day_masks,
+            per_day: hours.count() * minutes.count() * seconds.count(),
+            hours,
+            minutes,
+            seconds,
+        }
+    }
+
+    /// Returns the days matched by all of the date fields in a month, one bit per day.
+    pub(crate) fn day_mask(&self, year: Ordinal, month: Ordinal) -> u32 {
+        self.day_masks.get(year, month)
+    }
+
+    pub(crate) fn hours(&self) -> &TimeTable {
+        &self.hours
+    }
+
+    pub(crate) fn minutes(&self) -> &TimeTable {
+        &self.minutes
+    }
+
+    pub(crate) fn seconds(&self) -> &TimeTable {
+        &self.seconds
+    }
+
+    /// Returns the number of times of day the schedule matches.
+    pub(crate) fn per_day(&self) -> u64 {
+        self.per_day
+    }
+}
+
+impl fmt::Debug for Tables {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tables")
+            .field("hours", &self.hours)
+            .field("minutes", &self.minutes)
+            .field("seconds", &self.seconds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The ordinals of an hours, minutes or seconds field, one bit per ordinal.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TimeTable {
+    ordinals: u64,
+    all: bool,
+}
+
+impl TimeTable {
+    fn new(field: &impl TimeUnitSpec) -> TimeTable {
+        TimeTable {
+            ordinals: field
+                .iter()
+                .fold(0, |ordinals, ordinal| ordinals | 1 << ordinal),
+            all: field.is_all(),
+        }
+    }
+
+    pub(crate) fn includes(&self, ordinal: Ordinal) -> bool {
+        self.all || self.ordinals >> ordinal & 1 != 0
+    }
+
+    fn count(&self) -> u64 {
+        self.ordinals.count_ones() as u64
+    }
+}
+
+/// The days matched by a schedule's date fields in each supported month, one bit per day.
+///
+/// Schedules with the same date fields have the same masks, so they're shared between them.
+#[derive(Clone)]
+pub(crate) struct DayMasks {
+    masks: Arc<[u32]>,
+}
+
+impl DayMasks {
+    pub(crate) fn compile(schedule: &Schedule) -> DayMasks {
+        let masks = (Years::inclusive_min()..=Years::inclusive_max())
+            .flat_map(|year| (1..=12).map(move |month| (year, month)))
+            .map(|(year, month)| schedule.day_mask(year, month))
+            .collect();
+        DayMasks { masks }
+    }
+
+    /// Returns the mask for a month, which is empty outside of the supported years.
+    fn get(&self, year: Ordinal, month: Ordinal) -> u32 {
+        let Some(years) = year.checked_sub(Years::inclusive_min()) else {
+            return 0;
+        };
+        let index = (years * 12 + month - 1) as usize;
+        self.masks.get(index).copied().unwrap_or(0)
+    }
+}
+
+/// Iterates over the days set in a month's mask, from either end.
+pub(crate) struct MaskDays {
+    mask: u32,
+}
+
+impl MaskDays {
+    /// Iterates over the days in `mask` between `first` and `last` inclusive.
+    pub(crate) fn new(mask: u32, first: Ordinal, last: Ordinal) -> MaskDays {
+        let below_first = (1u32 << first) - 1;
+        let through_last = u32::MAX >> (31 - last);
+        MaskDays {
+            mask: mask & !below_first & through_last,
+        }
+    }
+}
+
+impl Iterator for MaskDays {
+    type Item = Ordinal;
+
+    fn next(&mut self) -> Option<Ordinal> {
+        if self.mask == 0 {
+            return None;
+        }
+        let day = self.mask.trailing_zeros();
+        self.mask &= self.mask - 1;
+        Some(day)
+    }
+}
+
+impl DoubleEndedIterator for MaskDays {
+    fn next_back(&mut self) -> Option<Ordinal> {
+        if self.mask == 0 {
+            return None;
+        }
+        let day = 31 - self.mask.leading_zeros();
+        self.mask &= !(1 << day);
+        Some(day)
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::schedule::dst_gap_end;
+
+/// A time a [Schedule](crate::Schedule) matches that a DST transition skips or repeats, as
+/// listed by [Schedule::dst_anomalies](crate::Schedule::dst_anomalies).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DstAnomaly<D> {
+    /// The wall clock time doesn't exist because the clocks spring forward over it.
+    ///
+    /// `earlier` and `later` are the wall clock time read with the UTC offsets from after and
+    /// before the transition. They are what the time would have been if the clocks had changed
+    /// early or late.
+    Skipped {
+        local: NaiveDateTime,
+        earlier: D,
+        later: D,
+    },
+    /// The wall clock time happens twice because the clocks fall back over it.
+    Repeated {
+        local: NaiveDateTime,
+        earlier: D,
+        later: D,
+    },
+}
+
+impl<D> DstAnomaly<D>
+where
+    D: ScheduleDateTime,
+{
+    /// Describes a skipped wall clock time from the instant its gap ends.
+    pub(crate) fn skipped<Z>(timezone: &Z, local: NaiveDateTime) -> Option<DstAnomaly<D>>
+    where
+        Z: ScheduleTimeZone<DateTime = D>,
+    {
+        let gap_end = dst_gap_end(timezone, &local)?;
+        let before_gap = gap_end.checked_add_signed(-Duration::nanoseconds(1))?;
+        let earlier = gap_end.checked_add_signed(local - gap_end.naive_local())?;
+        let shift = gap_end.utc_offset_seconds() - before_gap.utc_offset_seconds();
+        let later = earlier.checked_add_signed(Duration::seconds(shift as i64))?;
+        Some(DstAnomaly::Skipped {
+            local,
+            earlier,
+            later,
+        })
+    }
+
+    /// Returns the wall clock time the schedule matches.
+    pub fn local(&self) -> &NaiveDateTime {
+        match self {
+            DstAnomaly::Skipped { local, .. } | DstAnomaly::Repeated { local, .. } => local,
+        }
+    }
+
+    /// Returns the earlier of the two candidate instants.
+    pub fn earlier(&self) -> &D {
+        match self {
+            DstAnomaly::Skipped { earlier, .. } | DstAnomaly::Repeated { earlier, .. } => earlier,
+        }
+    }
+
+    /// Returns the later of the two candidate instants.
+    pub fn later(&self) -> &D {
+        match self {
+            DstAnomaly::Skipped { later, .. } | DstAnomaly::Repeated { later, .. } => later,
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use std::{error, fmt};
+use alloc::string::String;
+use core::{error, fmt};
 
 /// A cron error
 #[derive(Debug)]
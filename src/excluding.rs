@@ -0,0 +1,495 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Utc};
+use core::iter::Rev;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
+use core::ops::RangeBounds;
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::clock::Clock;
+use crate::schedule::{DstFoldPolicy, Schedule};
+use crate::within::{RangeSteps, SearchWithin, Steps};
+
+/// Inclusion–exclusion counts every combination of exclusion schedules, which is 2^n counts for
+/// n exclusions, so eight of them already take 256. Past that it's cheaper to check each match of
+/// the base schedule.
+const MAX_COMBINED_EXCLUSIONS: usize = 8;
+
+/// A [Schedule] with blackout windows: the base schedule fires except at the times matched by
+/// any of its exclusion schedules or within any of its excluded intervals.
+///
+/// An exclusion schedule removes the fires it [includes](Schedule::includes), so `* * 2-3 * * ?`
+/// blacks out the whole of 02:00 to 03:59 and `0 0 0 25 12 ?` only removes a fire at midnight
+/// on Christmas. Excluded intervals are instants, so they apply the same way in every time zone.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{ExcludingSchedule, Schedule};
+/// use std::str::FromStr;
+///
+/// let every_five_minutes = Schedule::from_str("0 0/5 * * * ?").unwrap();
+/// let maintenance = Schedule::from_str("* * 2-3 * * ?").unwrap();
+/// let schedule = ExcludingSchedule::new(every_five_minutes).with_exclusion(maintenance);
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 1, 55, 0).unwrap();
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 4, 0, 0).single(), schedule.next_after(&start));
+/// assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 1, 1, 2, 30, 0).unwrap()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExcludingSchedule {
+    base: Schedule,
+    exclusions: Vec<Schedule>,
+    excluded_intervals: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    /// The excluded intervals in UTC, sorted and with overlapping or touching intervals joined,
+    /// so skipping past one never lands in another.
+    merged_intervals: Vec<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl ExcludingSchedule {
+    /// Creates a schedule that fires like `base` until exclusions are added.
+    pub fn new(base: Schedule) -> ExcludingSchedule {
+        ExcludingSchedule {
+            base,
+            exclusions: Vec::new(),
+            excluded_intervals: Vec::new(),
+            merged_intervals: Vec::new(),
+        }
+    }
+
+    /// Removes the fires that `exclusion` includes.
+    pub fn with_exclusion(mut self, exclusion: Schedule) -> ExcludingSchedule {
+        self.exclusions.push(exclusion);
+        self
+    }
+
+    /// Removes the fires from `start` up to but not including `end`.
+    pub fn with_excluded_interval<Z>(mut self, start: DateTime<Z>, end: DateTime<Z>) -> Self
+    where
+        Z: TimeZone,
+    {
+        self.excluded_intervals
+            .push((start.with_timezone(&Utc), end.with_timezone(&Utc)));
+        self.merged_intervals = merge_intervals(&self.excluded_intervals);
+        self
+    }
+
+    /// Returns the schedule the exclusions are removed from.
+    pub fn base(&self) -> &Schedule {
+        &self.base
+    }
+
+    /// Returns the exclusion schedules, in the order they were added.
+    pub fn exclusions(&self) -> &[Schedule] {
+        &self.exclusions
+    }
+
+    /// Returns the excluded intervals as start and exclusive end instants, in the order they
+    /// were added.
+    pub fn excluded_intervals(&self) -> &[(DateTime<Utc>, DateTime<Utc>)] {
+        &self.excluded_intervals
+    }
+
+    /// Returns true if the base schedule fires at `date_time` and no exclusion removes it.
+    pub fn includes<D>(&self, date_time: D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        self.interval_containing(&date_time).is_none()
+            && !self.excluded_by_schedule(&date_time)
+            && self.base.includes(date_time)
+    }
+
+    /// Returns the first DateTime that matches the schedule strictly after `after`.
+    pub fn next_after<D>(&self, after: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.first_within(Excluded(after), Unbounded)
+    }
+
+    /// Returns the last DateTime that matches the schedule strictly before `before`.
+    pub fn prev_before<D>(&self, before: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.last_within(Unbounded, Excluded(before))
+    }
+
+    /// Provides an iterator which will return each DateTime that matches the schedule starting with
+    /// the current time if applicable.
+    #[cfg(feature = "std")]
+    pub fn upcoming<Z>(&self, timezone: Z) -> ExcludingScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+    {
+        self.upcoming_with_clock(timezone, &crate::clock::SystemClock)
+    }
+
+    /// Like the `upcoming` method, but reads the current time from `clock`.
+    pub fn upcoming_with_clock<Z, C>(
+        &self,
+        timezone: Z,
+        clock: &C,
+    ) -> ExcludingScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        ExcludingScheduleIterator {
+            steps: Steps::new(self, timezone.at_utc(&clock.now().naive_utc())),
+        }
+    }
+
+    /// Like the `upcoming` method, but allows you to specify a start time other than the present.
+    pub fn after<D>(&self, after: &D) -> ExcludingScheduleIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+    {
+        ExcludingScheduleIterator {
+            steps: Steps::new(self, Some(after.clone())),
+        }
+    }
+
+    /// Provides an iterator over the DateTimes that match the schedule before `before`, from the
+    /// latest to the earliest.
+    pub fn before<D>(&self, before: &D) -> Rev<ExcludingScheduleIterator<'_, D::TimeZone>>
+    where
+        D: ScheduleDateTime,
+    {
+        self.after(before).rev()
+    }
+
+    /// Provides an iterator over the DateTimes that match the schedule within `range`, which is
+    /// interpreted like [Schedule::between].
+    pub fn between<D, R>(&self, range: R) -> ExcludingScheduleRangeIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        ExcludingScheduleRangeIterator {
+            steps: RangeSteps::new(
+                self,
+                range.start_bound().cloned(),
+                range.end_bound().cloned(),
+            ),
+        }
+    }
+
+    /// Counts the DateTimes that match the schedule within `range`, which is interpreted like
+    /// [Schedule::between].
+    ///
+    /// Excluded intervals are subtracted with [Schedule::count_between]. So are exclusion
+    /// schedules, by counting the matches the base schedule has in common with each combination
+    /// of them, as long as none of the schedules has a [QuarterDay](crate::QuarterDay), an
+    /// [AnchoredStep](crate::AnchoredStep) or a month end or DST policy other than the default.
+    /// That's 2^n counts for n exclusion schedules, so with more than eight of them, or with
+    /// schedules that can't be combined, the matches of the base schedule are checked one at a
+    /// time instead.
+    pub fn count_between<D, R>(&self, range: R) -> u64
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+        let timezone = match (&start, &end) {
+            (Included(date_time) | Excluded(date_time), _)
+            | (_, Included(date_time) | Excluded(date_time)) => date_time.time_zone(),
+            (Unbounded, Unbounded) => return 0,
+        };
+        let mut count = self.count_without_intervals(start.clone(), end.clone());
+        for &(from, until) in &self.merged_intervals {
+            // An end the time zone can't represent is either before everything it can, or
+            // after it.
+            let from = match timezone.at_utc(&from) {
+                Some(from) => Included(from),
+                None if from.year() < 0 => Unbounded,
+                None => continue,
+            };
+            let until = match timezone.at_utc(&until) {
+                Some(until) => Excluded(until),
+                None if until.year() >= 0 => Unbounded,
+                None => continue,
+            };
+            let start = later_start(start.clone(), from);
+            let end = earlier_end(end.clone(), until);
+            count -= self.count_without_intervals(start, end);
+        }
+        count
+    }
+
+    /// Counts the matches of the base schedule within the range that no exclusion schedule
+    /// includes.
+    fn count_without_intervals<D>(&self, start: Bound<D>, end: Bound<D>) -> u64
+    where
+        D: ScheduleDateTime,
+    {
+        let range = (start, end);
+        if self.exclusions.len() > MAX_COMBINED_EXCLUSIONS {
+            return self.count_by_scanning(range);
+        }
+        // Each combination of exclusions is counted through the schedule that fires when the
+        // base and all of them do, adding or subtracting by the size of the combination.
+        let mut combined = vec![(self.base.clone(), true)];
+        for exclusion in &self.exclusions {
+            for index in 0..combined.len() {
+                let (schedule, adds) = &combined[index];
+                let Some(both) = schedule.intersection(exclusion) else {
+                    return self.count_by_scanning(range);
+                };
+                combined.push((both, !adds));
+            }
+        }
+        let count = combined
+            .iter()
+            .map(|(schedule, adds)| {
+                let count = schedule.count_between(range.clone()) as i64;
+                if *adds {
+                    count
+                } else {
+                    -count
+                }
+            })
+            .sum::<i64>();
+        count as u64
+    }
+
+    fn count_by_scanning<D>(&self, range: (Bound<D>, Bound<D>)) -> u64
+    where
+        D: ScheduleDateTime,
+    {
+        self.base
+            .between(range)
+            .filter(|date_time| !self.excluded_by_schedule(date_time))
+            .count() as u64
+    }
+
+    fn excluded_by_schedule<D>(&self, date_time: &D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        self.exclusions
+            .iter()
+            .any(|exclusion| exclusion.includes(date_time.clone()))
+    }
+
+    /// Returns the bounds, in UTC, of the merged excluded interval containing `date_time`.
+    fn interval_containing<D>(&self, date_time: &D) -> Option<(NaiveDateTime, NaiveDateTime)>
+    where
+        D: ScheduleDateTime,
+    {
+        let instant =
+            date_time.naive_local() - Duration::seconds(date_time.utc_offset_seconds() as i64);
+        let after = self
+            .merged_intervals
+            .partition_point(|(start, _)| *start <= instant);
+        let &(start, end) = self.merged_intervals.get(after.checked_sub(1)?)?;
+        (instant < end).then_some((start, end))
+    }
+
+    /// Returns where a forward search picks up after `candidate`, which an exclusion schedule
+    /// includes. That's the end of the run of times the exclusion matches, as long as the UTC
+    /// offset is the same at both ends of the run, or else just past `candidate`.
+    fn after_excluded_run<D>(&self, candidate: D) -> Bound<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let local = candidate.naive_local();
+        let run_end = self
+            .skippable_exclusions(&candidate)
+            .filter_map(|exclusion| exclusion.naive_run_end(local))
+            .max();
+        let end = run_end.and_then(|run_end| {
+            candidate
+                .checked_add_signed(run_end - local)
+                .filter(|end| end.naive_local() == run_end)
+        });
+        end.map_or(Excluded(candidate), Included)
+    }
+
+    /// Returns where a backward search picks up before `candidate`, mirroring
+    /// `after_excluded_run`.
+    fn before_excluded_run<D>(&self, candidate: D) -> Bound<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let local = candidate.naive_local();
+        let run_start = self
+            .skippable_exclusions(&candidate)
+            .filter_map(|exclusion| exclusion.naive_run_start(local))
+            .min();
+        let start = run_start.and_then(|run_start| {
+            candidate
+                .checked_add_signed(run_start - local)
+                .filter(|start| start.naive_local() == run_start)
+        });
+        Excluded(start.unwrap_or(candidate))
+    }
+
+    /// Returns the exclusion schedules that include `date_time` and remove every instant of the
+    /// wall clock times they match, so their runs can be skipped whole.
+    fn skippable_exclusions<'a, D>(&'a self, date_time: &'a D) -> impl Iterator<Item = &'a Schedule>
+    where
+        D: ScheduleDateTime,
+    {
+        self.exclusions.iter().filter(move |exclusion| {
+            exclusion.dst_fold_policy() == DstFoldPolicy::Both
+                && exclusion.includes(date_time.clone())
+        })
+    }
+}
+
+impl SearchWithin for ExcludingSchedule {
+    fn first_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let mut start = start.cloned();
+        loop {
+            let candidate = self.base.between((start, end.cloned())).next()?;
+            if let Some((_, end)) = self.interval_containing(&candidate) {
+                start = Included(candidate.time_zone().at_utc(&end)?);
+            } else if self.excluded_by_schedule(&candidate) {
+                start = self.after_excluded_run(candidate);
+            } else {
+                return Some(candidate);
+            }
+        }
+    }
+
+    fn last_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let mut end = end.cloned();
+        loop {
+            let candidate = self.base.between((start.cloned(), end)).next_back()?;
+            if let Some((start, _)) = self.interval_containing(&candidate) {
+                end = Excluded(candidate.time_zone().at_utc(&start)?);
+            } else if self.excluded_by_schedule(&candidate) {
+                end = self.before_excluded_run(candidate);
+            } else {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+impl From<Schedule> for ExcludingSchedule {
+    fn from(base: Schedule) -> ExcludingSchedule {
+        ExcludingSchedule::new(base)
+    }
+}
+
+/// Sorts the intervals in UTC and joins the ones that overlap or touch.
+fn merge_intervals(
+    intervals: &[(DateTime<Utc>, DateTime<Utc>)],
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut intervals = intervals
+        .iter()
+        .map(|(start, end)| (start.naive_utc(), end.naive_utc()))
+        .filter(|(start, end)| start < end)
+        .collect::<Vec<_>>();
+    intervals.sort();
+    let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Returns whichever start bound lets fewer DateTimes through.
+fn later_start<D>(a: Bound<D>, b: Bound<D>) -> Bound<D>
+where
+    D: ScheduleDateTime,
+{
+    match (&a, &b) {
+        (Unbounded, _) => b,
+        (_, Unbounded) => a,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => {
+            if x > y || (x == y && matches!(a, Excluded(_))) {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
+/// Returns whichever end bound lets fewer DateTimes through.
+fn earlier_end<D>(a: Bound<D>, b: Bound<D>) -> Bound<D>
+where
+    D: ScheduleDateTime,
+{
+    match (&a, &b) {
+        (Unbounded, _) => b,
+        (_, Unbounded) => a,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => {
+            if x < y || (x == y && matches!(a, Excluded(_))) {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
+/// An iterator over the DateTimes of an [ExcludingSchedule], forwards from a starting point or
+/// backwards with `rev`.
+pub struct ExcludingScheduleIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    steps: Steps<'a, ExcludingSchedule, Z>,
+}
+
+impl<Z> Iterator for ExcludingScheduleIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    type Item = Z::DateTime;
+
+    fn next(&mut self) -> Option<Z::DateTime> {
+        self.steps.next()
+    }
+}
+
+impl<Z> DoubleEndedIterator for ExcludingScheduleIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps.next_back()
+    }
+}
+
+/// An iterator over the DateTimes of an [ExcludingSchedule] within a range, from either end.
+pub struct ExcludingScheduleRangeIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    steps: RangeSteps<'a, ExcludingSchedule, Z>,
+}
+
+impl<Z> Iterator for ExcludingScheduleRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    type Item = Z::DateTime;
+
+    fn next(&mut self) -> Option<Z::DateTime> {
+        self.steps.next()
+    }
+}
+
+impl<Z> DoubleEndedIterator for ExcludingScheduleRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps.next_back()
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
+use core::hash::Hash;
+use std::collections::HashMap;
+
+use crate::backend::ScheduleDateTime;
+use crate::ordinal::Ordinal;
+use crate::schedule::{dst_gap_before, DstFoldPolicy, DstGapPolicy, MonthEndPolicy, Schedule};
+use crate::time_unit::{TimeUnitField, TimeUnitSpec, Years};
+
+/// The slots of the schedules that include one value of a field, one bit per slot.
+type Postings = Vec<u64>;
+
+/// Answers which of many schedules fire at a given instant.
+///
+/// Every value of every field keeps a bitset of the schedules that include it, so finding the
+/// matches for an instant intersects seven bitsets rather than checking each schedule in turn.
+/// Schedules restricted by weeks or days of the year, a [QuarterDay](crate::QuarterDay), an
+/// [AnchoredStep](crate::AnchoredStep), [MonthEndPolicy::Clamp] or a [DstFoldPolicy] other than
+/// firing at both instants are confirmed with [Schedule::includes] after the intersection.
+/// Schedules with [DstGapPolicy::ShiftForward] are also checked at the end of every DST gap.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{Schedule, ScheduleIndex};
+/// use std::str::FromStr;
+///
+/// let mut index = ScheduleIndex::new();
+/// index.insert("standup", Schedule::from_str("0 30 9 ? * Mon-Fri").unwrap());
+/// index.insert("backup", Schedule::from_str("0 0/30 * * * ?").unwrap());
+/// index.insert("report", Schedule::from_str("0 0 17 * * ?").unwrap());
+///
+/// let monday = Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap();
+/// assert_eq!(vec![&"standup", &"backup"], index.matching(&monday));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScheduleIndex<K> {
+    entries: Vec<Option<(K, Schedule)>>,
+    slots: HashMap<K, usize>,
+    free_slots: Vec<usize>,
+    seconds: Vec<Postings>,
+    minutes: Vec<Postings>,
+    hours: Vec<Postings>,
+    days_of_month: Vec<Postings>,
+    months: Vec<Postings>,
+    days_of_week: Vec<Postings>,
+    years: Vec<Postings>,
+    /// Schedules that have to be confirmed with [Schedule::includes].
+    filtered: Postings,
+    /// Schedules that can fire at the end of a DST gap without matching its wall clock time.
+    shifted_forward: Postings,
+}
+
+impl<K> Default for ScheduleIndex<K>
+where
+    K: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        ScheduleIndex::new()
+    }
+}
+
+impl<K> ScheduleIndex<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Creates an empty index.
+    pub fn new() -> ScheduleIndex<K> {
+        let postings = |count: usize| vec![Postings::new(); count];
+        ScheduleIndex {
+            entries: Vec::new(),
+            slots: HashMap::new(),
+            free_slots: Vec::new(),
+            seconds: postings(60),
+            minutes: postings(60),
+            hours: postings(24),
+            days_of_month: postings(31),
+            months: postings(12),
+            days_of_week: postings(7),
+            years: postings((Years::inclusive_max() - Years::inclusive_min() + 1) as usize),
+            filtered: Postings::new(),
+            shifted_forward: Postings::new(),
+        }
+    }
+
+    /// Adds `schedule` under `key`, returning the schedule it replaces, if any.
+    pub fn insert(&mut self, key: K, schedule: Schedule) -> Option<Schedule> {
+        let replaced = self.remove(&key);
+        let slot = match self.free_slots.pop() {
+            Some(slot) => slot,
+            None => {
+                self.entries.push(None);
+                if self.entries.len() > self.filtered.len() * 64 {
+                    self.for_each_postings(|postings| postings.push(0));
+                }
+                self.entries.len() - 1
+            }
+        };
+
+        let (word, bit) = (slot / 64, 1 << (slot % 64));
+        let clamped = schedule.month_end_policy() == MonthEndPolicy::Clamp;
+        add(&mut self.seconds, schedule.seconds().iter(), 0, word, bit);
+        add(&mut self.minutes, schedule.minutes().iter(), 0, word, bit);
+        add(&mut self.hours, schedule.hours().iter(), 0, word, bit);
+        // A clamped day can fall on any day of the month, so those are left to `includes`.
+        if clamped {
+            add(&mut self.days_of_month, 1..=31, 1, word, bit);
+        } else {
+            add(
+                &mut self.days_of_month,
+                schedule.days_of_month().iter(),
+                1,
+                word,
+                bit,
+            );
+        }
+        add(&mut self.months, schedule.months().iter(), 1, word, bit);
+        add(
+            &mut self.days_of_week,
+            schedule.days_of_week().iter(),
+            1,
+            word,
+            bit,
+        );
+        add(
+            &mut self.years,
+            schedule.years().iter(),
+            Years::inclusive_min(),
+            word,
+            bit,
+        );
+        if clamped
+            || !schedule.weeks_of_year().is_all()
+            || !schedule.days_of_year().is_all()
+            || schedule.quarter_day().is_some()
+            || schedule.anchored_step().is_some()
+            || schedule.dst_fold_policy() != DstFoldPolicy::Both
+        {
+            self.filtered[word] |= bit;
+        }
+        if schedule.dst_gap_policy() == DstGapPolicy::ShiftForward {
+            self.shifted_forward[word] |= bit;
+        }
+
+        self.slots.insert(key.clone(), slot);
+        self.entries[slot] = Some((key, schedule));
+        replaced
+    }
+
+    /// Removes the schedule stored under `key`, returning it if there was one.
+    pub fn remove(&mut self, key: &K) -> Option<Schedule> {
+        let slot = self.slots.remove(key)?;
+        let (word, bit) = (slot / 64, 1 << (slot % 64));
+        self.for_each_postings(|postings| postings[word] &= !bit);
+        self.free_slots.push(slot);
+        self.entries[slot].take().map(|(_, schedule)| schedule)
+    }
+
+    /// Returns the schedule stored under `key`.
+    pub fn get(&self, key: &K) -> Option<&Schedule> {
+        let slot = *self.slots.get(key)?;
+        self.entries[slot].as_ref().map(|(_, schedule)| schedule)
+    }
+
+    /// Returns the number of schedules in the index.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns true if the index has no schedules.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the keys of the schedules that include `date_time`, in the order the slots they
+    /// were stored in were first used.
+    pub fn matching<D>(&self, date_time: &D) -> Vec<&K>
+    where
+        D: ScheduleDateTime,
+    {
+        let local = date_time.naive_local();
+        let mut matches = self.matching_day(&local);
+        intersect(&mut matches, &self.hours[local.hour() as usize]);
+        intersect(&mut matches, &self.minutes[local.minute() as usize]);
+        intersect(&mut matches, &self.seconds[local.second() as usize]);
+        let mut unconfirmed = self.filtered.clone();
+        if dst_gap_before(date_time).is_some() {
+            union(&mut matches, &self.shifted_forward);
+            union(&mut unconfirmed, &self.shifted_forward);
+        }
+        self.keys(matches, &unconfirmed, |schedule| {
+            schedule.includes(date_time.clone())
+        })
+    }
+
+    /// Returns the keys of the schedules that fire at least once during the minute containing
+    /// `date_time`.
+    pub fn matching_minute<D>(&self, date_time: &D) -> Vec<&K>
+    where
+        D: ScheduleDateTime,
+    {
+        let local = date_time.naive_local();
+        let mut matches = self.matching_day(&local);
+        intersect(&mut matches, &self.hours[local.hour() as usize]);
+        intersect(&mut matches, &self.minutes[local.minute() as usize]);
+        let into_minute = Duration::seconds(local.second() as i64)
+            + Duration::nanoseconds(local.nanosecond() as i64);
+        let minute_start = date_time.checked_add_signed(-into_minute);
+        let gap_ends = minute_start
+            .as_ref()
+            .is_some_and(|minute_start| dst_gap_before(minute_start).is_some());
+        let mut unconfirmed = self.filtered.clone();
+        if gap_ends {
+            union(&mut matches, &self.shifted_forward);
+            union(&mut unconfirmed, &self.shifted_forward);
+        }
+        self.keys(matches, &unconfirmed, |schedule| {
+            let Some(minute_start) = &minute_start else {
+                return false;
+            };
+            let first_second = schedule.seconds().iter().next().and_then(|second| {
+                minute_start.checked_add_signed(Duration::seconds(second as i64))
+            });
+            (gap_ends && schedule.includes(minute_start.clone()))
+                || first_second.is_some_and(|date_time| schedule.includes(date_time))
+        })
+    }
+
+    /// Finds the schedules whose date fields include the date of `local`.
+    fn matching_day(&self, local: &NaiveDateTime) -> Postings {
+        let year = local.year() as Ordinal;
+        if !(Years::inclusive_min()..=Years::inclusive_max()).contains(&year) {
+            return vec![0; self.filtered.len()];
+        }
+        let mut matches = self.years[(year - Years::inclusive_min()) as usize].clone();
+        intersect(&mut matches, &self.months[local.month0() as usize]);
+        intersect(&mut matches, &self.days_of_month[local.day0() as usize]);
+        intersect(
+            &mut matches,
+            &self.days_of_week[local.weekday().num_days_from_sunday() as usize],
+        );
+        matches
+    }
+
+    fn keys<F>(&self, matches: Postings, unconfirmed: &Postings, confirm: F) -> Vec<&K>
+    where
+        F: Fn(&Schedule) -> bool,
+    {
+        let mut keys = Vec::new();
+        for (word, (&bits, &filtered)) in matches.iter().zip(unconfirmed).enumerate() {
+            let mut bits = bits;
+            while bits != 0 {
+                let bit = bits.trailing_zeros();
+                bits &= bits - 1;
+                let Some((key, schedule)) = &self.entries[word * 64 + bit as usize] else {
+                    continue;
+                };
+                if filtered & (1 << bit) == 0 || confirm(schedule) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    fn for_each_postings<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Postings),
+    {
+        self.seconds
+            .iter_mut()
+            .chain(&mut self.minutes)
+            .chain(&mut self.hours)
+            .chain(&mut self.days_of_month)
+            .chain(&mut self.months)
+            .chain(&mut self.days_of_week)
+            .chain(&mut self.years)
+            .chain(Some(&mut self.filtered))
+            .chain(Some(&mut self.shifted_forward))
+            .for_each(&mut f);
+    }
+}
+
+fn add<I>(postings: &mut [Postings], ordinals: I, min: Ordinal, word: usize, bit: u64)
+where
+    I: IntoIterator<Item = Ordinal>,
+{
+    for ordinal in ordinals {
+        postings[(ordinal - min) as usize][word] |= bit;
+    }
+}
+
+fn intersect(matches: &mut Postings, postings: &Postings) {
+    for (word, &other) in matches.iter_mut().zip(postings) {
+        *word &= other;
+    }
+}
+
+fn union(matches: &mut Postings, postings: &Postings) {
+    for (word, &other) in matches.iter_mut().zip(postings) {
+        *word |= other;
+    }
+}
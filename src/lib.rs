@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(rust_2018_idioms)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![allow(clippy::needless_doctest_main)]
@@ -8,6 +9,7 @@
 //! use chrono::Utc;
 //! use std::str::FromStr;
 //!
+//! # #[cfg(feature = "std")]
 //! fn main() {
 //!   //               sec  min   hour   day of month   month   day of week   year
 //!   let expression = "0   30   9,12,15     1,15       May-Aug  Mon,Wed,Fri  2018/2";
@@ -17,6 +19,8 @@
 //!     println!("-> {}", datetime);
 //!   }
 //! }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //!
 //! /*
 //! Upcoming fire times:
@@ -32,16 +36,67 @@
 //! -> 2018-08-15 09:30:00 UTC
 //! */
 //! ```
+//!
+//! # Features
+//! Finding the current time, batch evaluation and `ScheduleIndex` need the default `std`
+//! feature. Since 0.18, builds with `default-features = false` only need `alloc` and leave
+//! these out unless `std` is enabled again.
+
+extern crate alloc;
 
 /// Error types used by this crate.
 pub mod error;
 
+mod anchored_step;
+mod backend;
+#[cfg(feature = "std")]
+mod batch;
+mod clock;
+mod compiled;
+mod dst_anomaly;
+mod excluding;
+#[cfg(feature = "std")]
+mod index;
+mod multi_schedule;
 mod ordinal;
 mod parsing;
+mod quarter;
 mod queries;
 mod schedule;
+mod schedule_set;
 mod specifier;
 mod time_unit;
+mod utc_translation;
+mod within;
+#[cfg(feature = "chrono-tz")]
+mod zoned;
 
-pub use crate::schedule::{OwnedScheduleIterator, Schedule, ScheduleIterator};
+pub use crate::anchored_step::{AnchoredStep, StepUnit};
+#[cfg(feature = "jiff")]
+pub use crate::backend::JiffZone;
+#[cfg(feature = "time")]
+pub use crate::backend::OffsetZone;
+pub use crate::backend::{NaiveZone, ScheduleDateTime, ScheduleTimeZone};
+#[cfg(feature = "std")]
+pub use crate::batch::{next_after_each, next_after_each_parallel};
+#[cfg(feature = "std")]
+pub use crate::clock::SystemClock;
+pub use crate::clock::{Clock, MockClock};
+pub use crate::compiled::CompiledSchedule;
+pub use crate::dst_anomaly::DstAnomaly;
+pub use crate::excluding::{
+    ExcludingSchedule, ExcludingScheduleIterator, ExcludingScheduleRangeIterator,
+};
+#[cfg(feature = "std")]
+pub use crate::index::ScheduleIndex;
+pub use crate::multi_schedule::MultiScheduleIterator;
+pub use crate::quarter::QuarterDay;
+pub use crate::schedule::{
+    DstFoldPolicy, DstGapPolicy, MonthEndPolicy, NaiveScheduleIterator, OwnedScheduleIterator,
+    Schedule, ScheduleIterator, ScheduleRangeIterator,
+};
+pub use crate::schedule_set::{ScheduleSet, ScheduleSetIterator, ScheduleSetRangeIterator};
 pub use crate::time_unit::TimeUnitSpec;
+pub use crate::utc_translation::{LossReason, TranslationLoss, UtcSchedule, UtcTranslation};
+#[cfg(feature = "chrono-tz")]
+pub use crate::zoned::ZonedSchedule;
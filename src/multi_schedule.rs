@@ -0,0 +1,172 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::schedule::{OwnedScheduleIterator, Schedule};
+
+/// An iterator over the DateTimes of many schedules merged into one timeline, yielding the id
+/// of the schedule each DateTime came from.
+///
+/// Like [ScheduleIterator](crate::ScheduleIterator), it iterates forwards from its starting
+/// point with `next` and backwards from it with `next_back`, so `.rev()` gives the DateTimes
+/// before the start from the latest to the earliest. DateTimes are compared as instants, so
+/// both passes through a DST fold come out in the order they happen. Schedules that match the
+/// same instant are yielded in the order they were given, or in the opposite order when
+/// iterating backwards, so a reverse iteration is exactly the forward one reversed.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{MultiScheduleIterator, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedules = vec![
+///     ("hourly", Schedule::from_str("0 0 * * * ?").unwrap()),
+///     ("half past", Schedule::from_str("0 30 * * * ?").unwrap()),
+/// ];
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let mut timeline = MultiScheduleIterator::after(schedules.clone(), &start);
+/// assert_eq!(Some(("half past", Utc.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap())), timeline.next());
+/// assert_eq!(Some(("hourly", Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap())), timeline.next());
+///
+/// let mut earlier = MultiScheduleIterator::after(schedules, &start).rev();
+/// assert_eq!(Some(("half past", Utc.with_ymd_and_hms(2023, 12, 31, 23, 30, 0).unwrap())), earlier.next());
+/// ```
+pub struct MultiScheduleIterator<K, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    ids: Vec<K>,
+    forward: Vec<OwnedScheduleIterator<Z>>,
+    backward: Vec<OwnedScheduleIterator<Z>>,
+    /// The next DateTime of each schedule going forwards, filled on the first call to `next`.
+    later: Option<BinaryHeap<Reverse<Pending<Z>>>>,
+    /// The previous DateTime of each schedule going backwards, filled on the first call to
+    /// `next_back`.
+    earlier: Option<BinaryHeap<Pending<Z>>>,
+}
+
+impl<K, Z> MultiScheduleIterator<K, Z>
+where
+    K: Clone,
+    Z: ScheduleTimeZone,
+{
+    /// Merges the DateTimes of each schedule after `after`, from the earliest to the latest, or
+    /// before it, from the latest to the earliest, when reversed.
+    pub fn after<I, D>(schedules: I, after: &D) -> Self
+    where
+        I: IntoIterator<Item = (K, Schedule)>,
+        D: ScheduleDateTime<TimeZone = Z>,
+    {
+        let mut ids = Vec::new();
+        let mut forward = Vec::new();
+        let mut backward = Vec::new();
+        for (id, schedule) in schedules {
+            ids.push(id);
+            forward.push(schedule.after_owned(after.clone()));
+            backward.push(schedule.after_owned(after.clone()));
+        }
+        MultiScheduleIterator {
+            ids,
+            forward,
+            backward,
+            later: None,
+            earlier: None,
+        }
+    }
+}
+
+impl<K, Z> Iterator for MultiScheduleIterator<K, Z>
+where
+    K: Clone,
+    Z: ScheduleTimeZone,
+{
+    type Item = (K, Z::DateTime);
+
+    fn next(&mut self) -> Option<(K, Z::DateTime)> {
+        let forward = &mut self.forward;
+        let later = self.later.get_or_insert_with(|| {
+            forward
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, iterator)| Pending::new(iterator.next(), index))
+                .map(Reverse)
+                .collect()
+        });
+        let Reverse(Pending { date_time, index }) = later.pop()?;
+        if let Some(next) = Pending::new(self.forward[index].next(), index) {
+            later.push(Reverse(next));
+        }
+        Some((self.ids[index].clone(), date_time))
+    }
+}
+
+impl<K, Z> DoubleEndedIterator for MultiScheduleIterator<K, Z>
+where
+    K: Clone,
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<(K, Z::DateTime)> {
+        let backward = &mut self.backward;
+        let earlier = self.earlier.get_or_insert_with(|| {
+            backward
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, iterator)| Pending::new(iterator.next_back(), index))
+                .collect()
+        });
+        let Pending { date_time, index } = earlier.pop()?;
+        if let Some(prev) = Pending::new(self.backward[index].next_back(), index) {
+            earlier.push(prev);
+        }
+        Some((self.ids[index].clone(), date_time))
+    }
+}
+
+/// The next DateTime of one of the schedules, ordered by instant and then by the order the
+/// schedules were given in.
+struct Pending<Z>
+where
+    Z: ScheduleTimeZone,
+{
+    date_time: Z::DateTime,
+    index: usize,
+}
+
+impl<Z> Pending<Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn new(date_time: Option<Z::DateTime>, index: usize) -> Option<Pending<Z>> {
+        date_time.map(|date_time| Pending { date_time, index })
+    }
+}
+
+impl<Z> PartialEq for Pending<Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Z> Eq for Pending<Z> where Z: ScheduleTimeZone {}
+
+impl<Z> PartialOrd for Pending<Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Z> Ord for Pending<Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.date_time, self.index).cmp(&(&other.date_time, other.index))
+    }
+}
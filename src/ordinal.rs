@@ -1,8 +1,357 @@
-use std::collections::BTreeSet;
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::RangeBounds;
 
 pub type Ordinal = u32;
-// TODO: Make OrdinalSet an enum.
-// It should either be a BTreeSet of ordinals or an `All` option to save space.
-// `All` can iterate from inclusive_min to inclusive_max and answer membership
-// queries
-pub type OrdinalSet = BTreeSet<Ordinal>;
+
+const WORD_BITS: Ordinal = u64::BITS;
+
+/// Converts a year to an ordinal for searching the years field. Years before year 0, which no
+/// schedule matches, all become 0 rather than wrapping around.
+pub fn year_ordinal(year: i32) -> Ordinal {
+    year.max(0) as Ordinal
+}
+
+/// A set of ordinals from `MIN` upwards, stored as a bitmask of `WORDS` words.
+///
+/// Every field has a set sized for its own range: seconds, minutes, hours, days of the month and
+/// months fit in a single word, the days of the year need six and years are stored from 1970 so
+/// the supported years fit in three. Sets with the same ordinals are always stored identically,
+/// so they can be compared and hashed directly.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrdinalSet<const MIN: Ordinal, const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> OrdinalSet<MIN, WORDS> {
+    /// The largest ordinal the set can hold.
+    const MAX: Ordinal = MIN + WORD_BITS * WORDS as Ordinal - 1;
+
+    pub const fn new() -> Self {
+        OrdinalSet { words: [0; WORDS] }
+    }
+
+    /// Builds the set of every ordinal from `min` to `max` inclusive. Usable in statics, where
+    /// a range the set can't hold fails to compile.
+    pub const fn inclusive_range(min: Ordinal, max: Ordinal) -> Self {
+        assert!(
+            MIN <= min && max <= Self::MAX,
+            "range does not fit in the set"
+        );
+        let mut words = [0; WORDS];
+        let mut index = min - MIN;
+        while index <= max - MIN {
+            words[(index / WORD_BITS) as usize] |= 1 << (index % WORD_BITS);
+            index += 1;
+        }
+        OrdinalSet { words }
+    }
+
+    /// Adds `ordinal` to the set, returning false if it was already present. Ordinals outside
+    /// the range the set can hold are rejected and also return false; fields validate their
+    /// ordinals before adding them.
+    pub fn insert(&mut self, ordinal: Ordinal) -> bool {
+        if !(MIN..=Self::MAX).contains(&ordinal) {
+            return false;
+        }
+        let index = ordinal - MIN;
+        let word = &mut self.words[(index / WORD_BITS) as usize];
+        let bit = 1 << (index % WORD_BITS);
+        let added = *word & bit == 0;
+        *word |= bit;
+        added
+    }
+
+    /// Returns a view of the ordinals for querying, which is the same for sets of every size.
+    pub fn view(&self) -> Ordinals<'_> {
+        Ordinals {
+            min: MIN,
+            words: &self.words,
+        }
+    }
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> Default for OrdinalSet<MIN, WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> fmt::Debug for OrdinalSet<MIN, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.view().fmt(f)
+    }
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> FromIterator<Ordinal> for OrdinalSet<MIN, WORDS> {
+    fn from_iter<I: IntoIterator<Item = Ordinal>>(iter: I) -> Self {
+        let mut set = OrdinalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> Extend<Ordinal> for OrdinalSet<MIN, WORDS> {
+    fn extend<I: IntoIterator<Item = Ordinal>>(&mut self, iter: I) {
+        for ordinal in iter {
+            self.insert(ordinal);
+        }
+    }
+}
+
+/// The operations the fields need from an [OrdinalSet] of any size.
+pub trait OrdinalStore: Clone + Default + FromIterator<Ordinal> {
+    /// Adds `ordinal`, returning false if it was already present.
+    fn insert(&mut self, ordinal: Ordinal) -> bool;
+
+    /// Returns a view of the ordinals for querying.
+    fn view(&self) -> Ordinals<'_>;
+}
+
+impl<const MIN: Ordinal, const WORDS: usize> OrdinalStore for OrdinalSet<MIN, WORDS> {
+    fn insert(&mut self, ordinal: Ordinal) -> bool {
+        OrdinalSet::insert(self, ordinal)
+    }
+
+    fn view(&self) -> Ordinals<'_> {
+        OrdinalSet::view(self)
+    }
+}
+
+/// A borrowed view of the ordinals in an [OrdinalSet], whatever its size.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ordinals<'a> {
+    min: Ordinal,
+    words: &'a [u64],
+}
+
+impl<'a> Ordinals<'a> {
+    fn span(&self) -> Ordinal {
+        WORD_BITS * self.words.len() as Ordinal
+    }
+
+    pub fn contains(&self, ordinal: &Ordinal) -> bool {
+        match ordinal.checked_sub(self.min) {
+            Some(index) if index < self.span() => {
+                self.words[(index / WORD_BITS) as usize] & (1 << (index % WORD_BITS)) != 0
+            }
+            _ => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Returns the smallest ordinal in the set that is greater than or equal to `ordinal`.
+    pub fn next_at_or_after(&self, ordinal: Ordinal) -> Option<Ordinal> {
+        let index = ordinal.saturating_sub(self.min);
+        if index >= self.span() {
+            return None;
+        }
+        let mut word = (index / WORD_BITS) as usize;
+        let mut bits = self.words[word] & (u64::MAX << (index % WORD_BITS));
+        loop {
+            if bits != 0 {
+                return Some(self.min + word as Ordinal * WORD_BITS + bits.trailing_zeros());
+            }
+            word += 1;
+            if word == self.words.len() {
+                return None;
+            }
+            bits = self.words[word];
+        }
+    }
+
+    /// Returns the largest ordinal in the set that is less than or equal to `ordinal`.
+    pub fn prev_at_or_before(&self, ordinal: Ordinal) -> Option<Ordinal> {
+        let index = ordinal.checked_sub(self.min)?.min(self.span() - 1);
+        let mut word = (index / WORD_BITS) as usize;
+        let mut bits = self.words[word] & (u64::MAX >> (WORD_BITS - 1 - index % WORD_BITS));
+        loop {
+            if bits != 0 {
+                return Some(
+                    self.min + word as Ordinal * WORD_BITS + (WORD_BITS - 1 - bits.leading_zeros()),
+                );
+            }
+            if word == 0 {
+                return None;
+            }
+            word -= 1;
+            bits = self.words[word];
+        }
+    }
+
+    /// Iterates over the ordinals in ascending order.
+    pub fn iter(self) -> Iter<'a> {
+        self.range(..)
+    }
+
+    /// Iterates over the ordinals within `range` in ascending order. Unlike
+    /// `BTreeSet::range`, an empty or reversed range produces an empty iterator.
+    pub fn range<R>(self, range: R) -> Iter<'a>
+    where
+        R: RangeBounds<Ordinal>,
+    {
+        let front = match range.start_bound() {
+            Included(&start) => Some(start),
+            Excluded(&start) => start.checked_add(1),
+            Unbounded => Some(Ordinal::MIN),
+        };
+        let back = match range.end_bound() {
+            Included(&end) => Some(end),
+            Excluded(&end) => end.checked_sub(1),
+            Unbounded => Some(Ordinal::MAX),
+        };
+        match (front, back) {
+            (Some(front), Some(back)) if front <= back => Iter {
+                set: self,
+                front,
+                back,
+                finished: false,
+            },
+            _ => Iter {
+                set: self,
+                front: 0,
+                back: 0,
+                finished: true,
+            },
+        }
+    }
+}
+
+impl fmt::Debug for Ordinals<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for Ordinals<'a> {
+    type Item = Ordinal;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterates over the ordinals of an [OrdinalSet] between two inclusive ends, from either side.
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    set: Ordinals<'a>,
+    front: Ordinal,
+    back: Ordinal,
+    finished: bool,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Ordinal;
+
+    fn next(&mut self) -> Option<Ordinal> {
+        if self.finished {
+            return None;
+        }
+        let ordinal = self
+            .set
+            .next_at_or_after(self.front)
+            .filter(|&ordinal| ordinal <= self.back);
+        match ordinal {
+            Some(ordinal) if ordinal < self.back => self.front = ordinal + 1,
+            _ => self.finished = true,
+        }
+        ordinal
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Ordinal> {
+        if self.finished {
+            return None;
+        }
+        let ordinal = self
+            .set
+            .prev_at_or_before(self.back)
+            .filter(|&ordinal| ordinal >= self.front);
+        match ordinal {
+            Some(ordinal) if ordinal > self.front => self.back = ordinal - 1,
+            _ => self.finished = true,
+        }
+        ordinal
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sets_start_at_their_minimum() {
+        let mut set: OrdinalSet<1970, 3> = [2100, 2000].iter().cloned().collect();
+        assert!(set.insert(1970));
+        assert!(!set.insert(2000));
+        assert_eq!(
+            vec![1970, 2000, 2100],
+            set.view().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            OrdinalSet::<1970, 3>::inclusive_range(1970, 2100)
+                .view()
+                .len(),
+            131
+        );
+        assert_eq!(set, [1970, 2100, 2000].iter().cloned().collect());
+        assert!(!set.view().contains(&1969));
+        assert_eq!(Some(1970), set.view().next_at_or_after(0));
+        assert_eq!(None, set.view().prev_at_or_before(1969));
+        assert_eq!(Some(2100), set.view().prev_at_or_before(u32::MAX));
+    }
+
+    #[test]
+    fn test_insert_outside_the_set_is_rejected() {
+        let mut set = OrdinalSet::<1, 1>::new();
+        assert!(!set.insert(0));
+        assert!(!set.insert(65));
+        assert!(set.insert(64));
+        assert_eq!(vec![64], set.view().iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_next_and_prev_cross_words() {
+        let set: OrdinalSet<0, 6> = [1, 63, 64, 200, 366].iter().cloned().collect();
+        let set = set.view();
+        assert_eq!(Some(1), set.next_at_or_after(0));
+        assert_eq!(Some(64), set.next_at_or_after(64));
+        assert_eq!(Some(200), set.next_at_or_after(65));
+        assert_eq!(None, set.next_at_or_after(367));
+        assert_eq!(Some(63), set.prev_at_or_before(63));
+        assert_eq!(Some(64), set.prev_at_or_before(199));
+        assert_eq!(Some(366), set.prev_at_or_before(u32::MAX));
+        assert_eq!(None, set.prev_at_or_before(0));
+        assert!(set.contains(&366));
+        assert!(!set.contains(&365));
+    }
+
+    #[test]
+    fn test_range_from_both_ends() {
+        let set = OrdinalSet::<0, 1>::inclusive_range(0, 59);
+        let set = set.view();
+        let mut range = set.range((Excluded(10), Included(14)));
+        assert_eq!(Some(11), range.next());
+        assert_eq!(Some(14), range.next_back());
+        assert_eq!(Some(13), range.next_back());
+        assert_eq!(Some(12), range.next());
+        assert_eq!(None, range.next());
+        assert_eq!(None, range.next_back());
+        assert_eq!(Some(0), set.range(..1).next_back());
+        assert_eq!(None, set.range(..0).next());
+        assert_eq!(None, set.range((Included(5), Excluded(2))).next());
+    }
+}
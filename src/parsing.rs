@@ -1,14 +1,22 @@
-use winnow::ascii::{alpha1, digit1, multispace0};
+use winnow::ascii::{alpha1, digit0, digit1, multispace0};
 use winnow::combinator::{alt, delimited, eof, opt, separated, separated_pair, terminated};
 use winnow::prelude::*;
-
-use std::borrow::Cow;
-use std::convert::TryFrom;
-use std::str::{self, FromStr};
-
+use winnow::stream::AsChar;
+use winnow::token::take_while;
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use chrono::NaiveDate;
+use core::convert::TryFrom;
+use core::str::{self, FromStr};
+
+use crate::anchored_step::{AnchoredStep, StepUnit};
 use crate::error::{Error, ErrorKind};
 use crate::ordinal::*;
-use crate::schedule::{Schedule, ScheduleFields};
+use crate::quarter::QuarterDay;
+use crate::schedule::{DstFoldPolicy, DstGapPolicy, MonthEndPolicy, Schedule, ScheduleFields};
 use crate::specifier::*;
 use crate::time_unit::*;
 
@@ -16,10 +24,25 @@ impl TryFrom<Cow<'_, str>> for Schedule {
     type Error = Error;
 
     fn try_from(expression: Cow<'_, str>) -> Result<Self, Self::Error> {
-        match schedule.parse(&expression) {
-            Ok(schedule_fields) => Ok(Schedule::new(expression.into_owned(), schedule_fields)), // Extract from winnow tuple
-            Err(parse_error) => Err(ErrorKind::Expression(format!("{parse_error}")).into()),
-        }
+        let parsed = {
+            let (fields_expression, mut options) = split_options(&expression);
+            let schedule_fields = schedule.parse(fields_expression).map_err(|parse_error| {
+                Error::from(ErrorKind::Expression(format!("{parse_error}")))
+            })?;
+            // Options are applied through the same setters as in code, then the expression is kept
+            // as written.
+            let parsed = Schedule::new(fields_expression.to_owned(), schedule_fields);
+            options.try_fold(parsed, |parsed, option| {
+                let (name, value) = option.split_once('=').ok_or_else(|| {
+                    Error::from(ErrorKind::Expression(format!(
+                        "'{}' is not a valid option. Options look like 'name=value'.",
+                        option.trim()
+                    )))
+                })?;
+                parsed.with_option(&name.trim().to_ascii_lowercase(), value.trim())
+            })?
+        };
+        Ok(parsed.with_source(expression.into_owned()))
     }
 }
 
@@ -47,6 +70,181 @@ impl FromStr for Schedule {
     }
 }
 
+/// Parses a single field expression, like `*/2` or `1-10,20`, into a time unit.
+pub(crate) fn parse_field<T>(expression: &str) -> Result<T, Error>
+where
+    T: TimeUnitField,
+{
+    terminated(field.try_map(T::from_field), eof)
+        .parse(expression)
+        .map_err(|parse_error| ErrorKind::Expression(format!("{parse_error}")).into())
+}
+
+/// The name of the option that sets the [AnchoredStep].
+pub(crate) const STEP: &str = "step";
+/// The name of the option that sets the weeks of the year.
+pub(crate) const WEEKS_OF_YEAR: &str = "weeks-of-year";
+/// The name of the option that sets the days of the year.
+pub(crate) const DAYS_OF_YEAR: &str = "days-of-year";
+/// The name of the option that sets the [QuarterDay].
+pub(crate) const QUARTER_DAY: &str = "quarter-day";
+/// The name of the option that sets the [MonthEndPolicy].
+pub(crate) const MONTH_END: &str = "month-end";
+/// The name of the option that sets the [DstGapPolicy].
+pub(crate) const DST_GAP: &str = "dst-gap";
+/// The name of the option that sets the [DstFoldPolicy].
+pub(crate) const DST_FOLD: &str = "dst-fold";
+
+pub(crate) const MONTH_END_POLICIES: [(&str, MonthEndPolicy); 2] = [
+    ("skip", MonthEndPolicy::Skip),
+    ("clamp", MonthEndPolicy::Clamp),
+];
+pub(crate) const DST_GAP_POLICIES: [(&str, DstGapPolicy); 2] = [
+    ("skip", DstGapPolicy::Skip),
+    ("shift-forward", DstGapPolicy::ShiftForward),
+];
+pub(crate) const DST_FOLD_POLICIES: [(&str, DstFoldPolicy); 3] = [
+    ("both", DstFoldPolicy::Both),
+    ("earlier-only", DstFoldPolicy::EarlierOnly),
+    ("later-only", DstFoldPolicy::LaterOnly),
+];
+
+/// The tokens for each [QuarterDay], which follow Quartz's `L` and `W` with a `Q` for quarter.
+pub(crate) const QUARTER_DAYS: [(&str, QuarterDay); 4] = [
+    ("1Q", QuarterDay::First),
+    ("LQ", QuarterDay::Last),
+    ("1WQ", QuarterDay::FirstWeekday),
+    ("LWQ", QuarterDay::LastWeekday),
+];
+
+/// Returns the [QuarterDay] written in the days of month field of `source`, if any.
+pub(crate) fn written_quarter_day(source: &str) -> Option<QuarterDay> {
+    let day_of_month = split_options(source).0.split_whitespace().nth(3)?;
+    quarter_day.parse(day_of_month).ok()
+}
+
+/// Returns the token for `quarter_day`, as written in the days of month field.
+pub(crate) fn quarter_day_text(quarter_day: QuarterDay) -> &'static str {
+    QUARTER_DAYS
+        .iter()
+        .find(|(_, known)| *known == quarter_day)
+        .map_or("1Q", |&(text, _)| text)
+}
+
+const STEP_UNITS: [(&str, StepUnit); 3] = [
+    ("d", StepUnit::Days),
+    ("w", StepUnit::Weeks),
+    ("m", StepUnit::Months),
+];
+
+/// Parses the value of a `step` option, like `2w@2024-01-02` for every other week counted from
+/// January 2nd 2024.
+pub(crate) fn anchored_step(value: &str) -> Result<AnchoredStep, Error> {
+    let invalid = || {
+        Error::from(ErrorKind::Expression(format!(
+            "'{value}' is not a valid value for the '{STEP}' option. Steps look like \
+             '2w@2024-01-02', with a unit of 'd', 'w' or 'm'."
+        )))
+    };
+    let (step, anchor) = value.split_once('@').ok_or_else(invalid)?;
+    let step = step.trim();
+    let (unit_at, _) = step.char_indices().next_back().ok_or_else(invalid)?;
+    let (step, unit) = step.split_at(unit_at);
+    let step = u32::from_str(step)
+        .ok()
+        .filter(|&step| step != 0)
+        .ok_or_else(invalid)?;
+    let unit = option_value(&STEP_UNITS, STEP, unit)?;
+    let anchor = NaiveDate::from_str(anchor.trim()).map_err(|_| invalid())?;
+    Ok(AnchoredStep::new(anchor, unit, step))
+}
+
+/// Writes an [AnchoredStep] the way the `step` option is parsed.
+pub(crate) fn anchored_step_text(step: &AnchoredStep) -> String {
+    let unit = STEP_UNITS
+        .iter()
+        .find(|(_, unit)| *unit == step.unit())
+        .map_or("d", |&(text, _)| text);
+    format!(
+        "{}{}@{}",
+        step.step(),
+        unit,
+        step.anchor().format("%Y-%m-%d")
+    )
+}
+
+/// Splits an expression into its fields and the options that follow them, like
+/// `0 30 2 * * ?; dst-gap=shift-forward`.
+fn split_options(expression: &str) -> (&str, impl Iterator<Item = &str>) {
+    let mut parts = expression.split(';');
+    let fields = parts.next().unwrap_or_default();
+    (fields, parts)
+}
+
+/// Looks up the value of the option `name` in its table of `values`.
+pub(crate) fn option_value<T: Copy>(
+    values: &[(&str, T)],
+    name: &str,
+    value: &str,
+) -> Result<T, Error> {
+    values
+        .iter()
+        .find(|(text, _)| text.eq_ignore_ascii_case(value))
+        .map(|&(_, value)| value)
+        .ok_or_else(|| {
+            ErrorKind::Expression(format!(
+                "'{value}' is not a valid value for the '{name}' option."
+            ))
+            .into()
+        })
+}
+
+/// Returns how `value` is written in an option, or `None` for the default, which is left out.
+pub(crate) fn option_text<T: PartialEq + Default>(
+    values: &[(&'static str, T)],
+    value: &T,
+) -> Option<&'static str> {
+    if *value == T::default() {
+        return None;
+    }
+    values
+        .iter()
+        .find(|(_, known)| known == value)
+        .map(|&(text, _)| text)
+}
+
+/// Rewrites `source` so that its option `name` has `value`, or drops the option if `value` is
+/// `None`. The options are kept in the order they were first set.
+pub(crate) fn with_option(source: &str, name: &str, value: Option<&str>) -> String {
+    let (fields, options) = split_options(source);
+    let mut options: Vec<Cow<'_, str>> = options
+        .map(str::trim)
+        .filter(|option| {
+            option
+                .split_once('=')
+                .is_none_or(|(option_name, _)| !option_name.trim().eq_ignore_ascii_case(name))
+        })
+        .map(Cow::Borrowed)
+        .collect();
+    if let Some(value) = value {
+        let position = split_options(source)
+            .1
+            .position(|option| {
+                option
+                    .split_once('=')
+                    .is_some_and(|(option_name, _)| option_name.trim().eq_ignore_ascii_case(name))
+            })
+            .unwrap_or(options.len());
+        options.insert(position, Cow::Owned(format!("{name}={value}")));
+    }
+    let mut source = fields.trim_end().to_owned();
+    for option in options {
+        source.push_str("; ");
+        source.push_str(&option);
+    }
+    source
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Field {
     pub specifiers: Vec<RootSpecifier>, // TODO: expose iterator?
@@ -70,10 +268,10 @@ where
         {
             return Ok(T::all());
         }
-        let mut ordinals = OrdinalSet::new();
+        let mut ordinals = T::Set::default();
         for specifier in field.specifiers {
-            let specifier_ordinals: OrdinalSet = T::ordinals_from_root_specifier(&specifier)?;
-            for ordinal in specifier_ordinals {
+            let specifier_ordinals = T::ordinals_from_root_specifier(&specifier)?;
+            for ordinal in specifier_ordinals.view().iter() {
                 ordinals.insert(T::validate_ordinal(ordinal)?);
             }
         }
@@ -88,7 +286,8 @@ fn ordinal(i: &mut &str) -> winnow::Result<u32> {
 }
 
 fn name(i: &mut &str) -> winnow::Result<String> {
-    delimited(multispace0, alpha1, multispace0)
+    // Names may end in digits to allow for quarters like `Q1`.
+    delimited(multispace0, (alpha1, digit0).take(), multispace0)
         .map(ToOwned::to_owned)
         .parse_next(i)
 }
@@ -176,6 +375,20 @@ fn field_with_any(i: &mut &str) -> winnow::Result<Field> {
     Ok(Field { specifiers })
 }
 
+fn quarter_day(i: &mut &str) -> winnow::Result<QuarterDay> {
+    delimited(
+        multispace0,
+        take_while(1.., AsChar::is_alphanum).verify_map(|token: &str| {
+            QUARTER_DAYS
+                .iter()
+                .find(|(text, _)| text.eq_ignore_ascii_case(token))
+                .map(|&(_, quarter_day)| quarter_day)
+        }),
+        multispace0,
+    )
+    .parse_next(i)
+}
+
 fn shorthand_yearly(i: &mut &str) -> winnow::Result<ScheduleFields> {
     "@yearly".parse_next(i)?;
     let fields = ScheduleFields::new(
@@ -261,7 +474,13 @@ fn longhand(i: &mut &str) -> winnow::Result<ScheduleFields> {
     let seconds = field.try_map(Seconds::from_field);
     let minutes = field.try_map(Minutes::from_field);
     let hours = field.try_map(Hours::from_field);
-    let days_of_month = field_with_any.try_map(DaysOfMonth::from_field);
+    // A quarter day like `LQ` matches any day of the month that's that day of its quarter.
+    let days_of_month = alt((
+        quarter_day.map(|quarter_day| (DaysOfMonth::all(), Some(quarter_day))),
+        field_with_any
+            .try_map(DaysOfMonth::from_field)
+            .map(|days_of_month| (days_of_month, None)),
+    ));
     let months = field.try_map(Months::from_field);
     let days_of_week = field_with_any.try_map(DaysOfWeek::from_field);
     let years = opt(field.try_map(Years::from_field));
@@ -279,6 +498,7 @@ fn longhand(i: &mut &str) -> winnow::Result<ScheduleFields> {
         .map(
             |(seconds, minutes, hours, days_of_month, months, days_of_week, years)| {
                 let years = years.unwrap_or_else(Years::all);
+                let (days_of_month, quarter_day) = days_of_month;
                 ScheduleFields::new(
                     seconds,
                     minutes,
@@ -288,6 +508,7 @@ fn longhand(i: &mut &str) -> winnow::Result<ScheduleFields> {
                     days_of_week,
                     years,
                 )
+                .with_quarter_day(quarter_day)
             },
         )
         .parse_next(i)
@@ -319,6 +540,15 @@ mod test {
         named_point.parse(expression).unwrap();
     }
 
+    #[test]
+    fn test_nom_valid_named_point_with_digits() {
+        let expression = "Q3";
+        assert_eq!(
+            named_point.parse(expression).unwrap(),
+            RootSpecifier::NamedPoint("Q3".to_owned())
+        );
+    }
+
     #[test]
     fn test_nom_invalid_named_point() {
         let expression = "8";
@@ -654,6 +884,30 @@ mod test {
         Schedule::from_str(expression).unwrap();
     }
 
+    #[test]
+    fn test_parse_field_week_parity() {
+        let odd: WeeksOfYear = parse_field("odd").unwrap();
+        let even: WeeksOfYear = parse_field("EVEN").unwrap();
+        assert_eq!(odd, parse_field("*/2").unwrap());
+        assert_eq!(even, parse_field("2/2").unwrap());
+        assert!(parse_field::<WeeksOfYear>("weekly").is_err());
+        assert!(parse_field::<WeeksOfYear>("54").is_err());
+        assert!(parse_field::<WeeksOfYear>("1 2").is_err());
+    }
+
+    #[test]
+    fn test_quarter_month_names() {
+        let fields = schedule.parse("0 0 0 1 Q1,Q4 ?").unwrap();
+        let months = Months::from_ordinal_set([1, 2, 3, 10, 11, 12].into_iter().collect());
+        assert_eq!(fields, schedule.parse("0 0 0 1 1-3,10-12 ?").unwrap());
+        assert_eq!(
+            months,
+            Months::from_field(field.parse("Q1,Q4").unwrap()).unwrap()
+        );
+        assert!(schedule.parse("0 0 0 1 Q5 ?").is_err());
+        assert!(schedule.parse("0 0 0 1 * Q1").is_err());
+    }
+
     /// Issue #59
     #[test]
     fn test_reject_invalid_interval() {
@@ -0,0 +1,61 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::ordinal::Ordinal;
+use crate::schedule::days_in_month;
+
+/// A day relative to the start or end of a quarter, resolved separately for every quarter.
+///
+/// Combine it with the `Q1`-`Q4` month names to single out a quarter. In an expression, it's
+/// written in the days of month field as `1Q`, `LQ`, `1WQ` or `LWQ`, so the schedule below can
+/// also be parsed from `0 0 18 LQ Q4 ?`.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{QuarterDay, Schedule};
+/// use std::str::FromStr;
+///
+/// let close_the_books = Schedule::from_str("0 0 18 ? Q4 ?")
+///     .unwrap()
+///     .with_quarter_day(QuarterDay::Last);
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 12, 31, 18, 0, 0).unwrap(), close_the_books.after(&start).next().unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuarterDay {
+    /// The first day of the quarter.
+    First,
+    /// The last day of the quarter.
+    Last,
+    /// The first Monday to Friday of the quarter.
+    FirstWeekday,
+    /// The last Monday to Friday of the quarter.
+    LastWeekday,
+}
+
+impl QuarterDay {
+    /// Returns true if the given date is this day of its quarter.
+    pub(crate) fn includes(&self, date: NaiveDate) -> bool {
+        let year = date.year() as Ordinal;
+        let first_month = date.month0() / 3 * 3 + 1;
+        let last_month = first_month + 2;
+        let day = match self {
+            QuarterDay::First | QuarterDay::FirstWeekday => {
+                NaiveDate::from_ymd_opt(date.year(), first_month, 1)
+            }
+            QuarterDay::Last | QuarterDay::LastWeekday => {
+                NaiveDate::from_ymd_opt(date.year(), last_month, days_in_month(last_month, year))
+            }
+        };
+        let Some(day) = day else {
+            return false;
+        };
+        let day = match (self, day.weekday()) {
+            (QuarterDay::FirstWeekday, Weekday::Sat) => day + chrono::Duration::days(2),
+            (QuarterDay::FirstWeekday, Weekday::Sun) => day + chrono::Duration::days(1),
+            (QuarterDay::LastWeekday, Weekday::Sat) => day - chrono::Duration::days(1),
+            (QuarterDay::LastWeekday, Weekday::Sun) => day - chrono::Duration::days(2),
+            _ => day,
+        };
+        day == date
+    }
+}
@@ -1,16 +1,13 @@
-use chrono::offset::TimeZone;
-use chrono::{DateTime, Datelike, Duration, Timelike};
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
 
-use crate::ordinal::Ordinal;
+use crate::backend::ScheduleDateTime;
+use crate::ordinal::{year_ordinal, Ordinal};
 use crate::time_unit::{DaysOfMonth, Hours, Minutes, Months, Seconds, TimeUnitField};
 
 // TODO: Possibility of one query struct?
 
-pub struct NextAfterQuery<Z>
-where
-    Z: TimeZone,
-{
-    initial_datetime: DateTime<Z>,
+pub struct NextAfterQuery {
+    initial_datetime: NaiveDateTime,
     first_month: bool,
     first_day_of_month: bool,
     first_hour: bool,
@@ -18,13 +15,10 @@ where
     first_second: bool,
 }
 
-impl<Z> NextAfterQuery<Z>
-where
-    Z: TimeZone,
-{
-    pub fn from(after: &DateTime<Z>) -> NextAfterQuery<Z> {
+impl NextAfterQuery {
+    pub fn from<D: ScheduleDateTime>(after: &D) -> NextAfterQuery {
         NextAfterQuery {
-            initial_datetime: after.clone(),
+            initial_datetime: after.naive_local(),
             first_month: true,
             first_day_of_month: true,
             first_hour: true,
@@ -35,7 +29,7 @@ where
 
     pub fn year_lower_bound(&self) -> Ordinal {
         // Unlike the other units, years will never wrap around.
-        self.initial_datetime.year() as u32
+        year_ordinal(self.initial_datetime.year())
     }
 
     pub fn month_lower_bound(&mut self) -> Ordinal {
@@ -103,11 +97,8 @@ where
     }
 } // End of impl
 
-pub struct PrevFromQuery<Z>
-where
-    Z: TimeZone,
-{
-    initial_datetime: DateTime<Z>,
+pub struct PrevFromQuery {
+    initial_datetime: NaiveDateTime,
     first_month: bool,
     first_day_of_month: bool,
     first_hour: bool,
@@ -115,15 +106,18 @@ where
     first_second: bool,
 }
 
-impl<Z> PrevFromQuery<Z>
-where
-    Z: TimeZone,
-{
-    pub fn from(before: &DateTime<Z>) -> PrevFromQuery<Z> {
-        let initial_datetime = if before.timestamp_subsec_nanos() > 0 {
-            before.clone()
+impl PrevFromQuery {
+    pub fn from<D: ScheduleDateTime>(before: &D) -> PrevFromQuery {
+        let local = before.naive_local();
+        // Stepping back as an instant keeps the local time right across DST transitions.
+        let initial_datetime = if local.nanosecond() > 0 {
+            local
         } else {
-            before.clone() - Duration::seconds(1)
+            before
+                .checked_add_signed(-Duration::seconds(1))
+                .map(|before| before.naive_local())
+                .or_else(|| local.checked_sub_signed(Duration::seconds(1)))
+                .unwrap_or(local)
         };
         PrevFromQuery {
             initial_datetime,
@@ -137,7 +131,7 @@ where
 
     pub fn year_upper_bound(&self) -> Ordinal {
         // Unlike the other units, years will never wrap around.
-        self.initial_datetime.year() as u32
+        year_ordinal(self.initial_datetime.year())
     }
 
     pub fn month_upper_bound(&mut self) -> Ordinal {
@@ -1,8 +1,16 @@
-use chrono::offset::{LocalResult, TimeZone};
-use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
-use std::cmp::{max, min};
-use std::fmt::{Display, Formatter, Result as FmtResult};
-use std::ops::Bound::{Included, Unbounded};
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use chrono::offset::LocalResult;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use core::cmp::{max, min, Ordering};
+use core::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::iter::Rev;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
+use core::ops::RangeBounds;
 
 #[cfg(feature = "serde")]
 use core::fmt;
@@ -12,9 +20,20 @@ use serde::{
     Deserialize, Serialize, Serializer,
 };
 
+use crate::anchored_step::AnchoredStep;
+use crate::backend::{NaiveZone, ScheduleDateTime, ScheduleTimeZone};
+use crate::clock::Clock;
+#[cfg(feature = "std")]
+use crate::clock::SystemClock;
+use crate::compiled::{MaskDays, Tables};
+use crate::dst_anomaly::DstAnomaly;
+use crate::error::{Error, ErrorKind};
 use crate::ordinal::*;
+use crate::parsing::{self, parse_field};
+use crate::quarter::QuarterDay;
 use crate::queries::*;
 use crate::time_unit::*;
+use crate::utc_translation::{self, UtcTranslation};
 
 impl From<Schedule> for String {
     fn from(schedule: Schedule) -> String {
@@ -33,10 +52,1061 @@ impl Schedule {
         Schedule { source, fields }
     }
 
-    fn next_after<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    /// Returns the first DateTime that matches the schedule strictly after `after`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * ?").unwrap();
+    /// let nine = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).single(), schedule.next_after(&nine));
+    /// assert_eq!(Some(nine), schedule.next_at_or_after(&nine));
+    /// ```
+    pub fn next_after<D>(&self, after: &D) -> Option<D>
     where
-        Z: TimeZone,
+        D: ScheduleDateTime,
     {
+        self.matcher().next_within(after, Unbounded)
+    }
+
+    /// Returns the first DateTime that matches the schedule at or after `date_time`, counting
+    /// `date_time` itself if it matches.
+    pub fn next_at_or_after<D>(&self, date_time: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().next_at_or_after(date_time)
+    }
+
+    /// Returns the last DateTime that matches the schedule strictly before `before`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * ?").unwrap();
+    /// let nine = Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).single(), schedule.prev_before(&nine));
+    /// assert_eq!(Some(nine), schedule.prev_at_or_before(&nine));
+    /// ```
+    pub fn prev_before<D>(&self, before: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().prev_within(before, Unbounded)
+    }
+
+    /// Returns the last DateTime that matches the schedule at or before `date_time`, counting
+    /// `date_time` itself if it matches.
+    pub fn prev_at_or_before<D>(&self, date_time: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().prev_at_or_before(date_time)
+    }
+
+    /// Provides an iterator which will return each DateTime that matches the schedule starting with
+    /// the current time if applicable.
+    #[cfg(feature = "std")]
+    pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+    {
+        self.upcoming_with_clock(timezone, &SystemClock)
+    }
+
+    /// The same, but with an iterator with a static ownership
+    #[cfg(feature = "std")]
+    pub fn upcoming_owned<Z: ScheduleTimeZone>(&self, timezone: Z) -> OwnedScheduleIterator<Z> {
+        self.upcoming_owned_with_clock(timezone, &SystemClock)
+    }
+
+    /// Like the `upcoming` method, but reads the current time from `clock`.
+    pub fn upcoming_with_clock<Z, C>(&self, timezone: Z, clock: &C) -> ScheduleIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        ScheduleIterator::new(self.matcher(), timezone.at_utc(&clock.now().naive_utc()))
+    }
+
+    /// The same, but with an iterator with a static ownership
+    pub fn upcoming_owned_with_clock<Z, C>(
+        &self,
+        timezone: Z,
+        clock: &C,
+    ) -> OwnedScheduleIterator<Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        OwnedScheduleIterator::starting_at(
+            self.clone(),
+            None,
+            timezone.at_utc(&clock.now().naive_utc()),
+        )
+    }
+
+    /// Like the `upcoming` method, but allows you to specify a start time other than the present.
+    pub fn after<D>(&self, after: &D) -> ScheduleIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+    {
+        ScheduleIterator::new(self.matcher(), Some(after.clone()))
+    }
+
+    /// Provides an iterator over the wall clock times that match the schedule after `after`,
+    /// with no time zone and so no DST transitions to skip or repeat.
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 2 * * ?").unwrap();
+    /// let after = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let mut fires = schedule.after_naive(&after);
+    /// assert_eq!(after.date().and_hms_opt(2, 30, 0), fires.next());
+    /// assert!(schedule.includes_naive(after.date().and_hms_opt(2, 30, 0).unwrap()));
+    /// ```
+    pub fn after_naive(&self, after: &NaiveDateTime) -> NaiveScheduleIterator<'_> {
+        self.after(after)
+    }
+
+    /// The same, but with a static ownership.
+    pub fn after_owned<D: ScheduleDateTime>(&self, after: D) -> OwnedScheduleIterator<D::TimeZone> {
+        OwnedScheduleIterator::new(self.clone(), after)
+    }
+
+    /// Provides an iterator which will return each DateTime that matches the schedule before
+    /// `before`, from the latest to the earliest.
+    ///
+    /// This is the same as calling `rev()` on the iterator returned by [Schedule::after].
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("@daily").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 3, 12, 0, 0).unwrap();
+    /// let mut previous = schedule.before(&start);
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).single(), previous.next());
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).single(), previous.next());
+    /// ```
+    pub fn before<D>(&self, before: &D) -> Rev<ScheduleIterator<'_, D::TimeZone>>
+    where
+        D: ScheduleDateTime,
+    {
+        self.after(before).rev()
+    }
+
+    /// The same, but with a static ownership.
+    pub fn before_owned<D: ScheduleDateTime>(
+        &self,
+        before: D,
+    ) -> Rev<OwnedScheduleIterator<D::TimeZone>> {
+        self.after_owned(before).rev()
+    }
+
+    /// Provides an iterator over the DateTimes that match the schedule within `range`.
+    ///
+    /// Either end of the range can be inclusive or exclusive, and the iterator can be walked
+    /// from both ends. An unbounded end stops at the first or last year a schedule can match.
+    /// A range without either end has no timezone to search in, so it yields nothing.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::ops::Bound::{Excluded, Included};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    ///
+    /// assert_eq!(3, schedule.between(start..end).count());
+    /// assert_eq!(4, schedule.between(start..=end).count());
+    /// assert_eq!(2, schedule.between((Excluded(start), Excluded(end))).count());
+    /// assert_eq!(Some(end), schedule.between((Excluded(start), Included(end))).next_back());
+    /// ```
+    pub fn between<D, R>(&self, range: R) -> ScheduleRangeIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        ScheduleRangeIterator::new(
+            self.matcher(),
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        )
+    }
+
+    /// Counts the DateTimes that match the schedule within `range`, without visiting each one.
+    ///
+    /// Whole years, months and days with one UTC offset are counted from the number of days they
+    /// match and the number of hours, minutes and seconds in the schedule. Only the partial days
+    /// at either end of the range and days with a DST transition are scanned one match at a
+    /// time. The range is interpreted like [Schedule::between].
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let every_second = Schedule::from_str("* * * * * *").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(366 * 24 * 60 * 60, every_second.count_between(start..end));
+    /// ```
+    pub fn count_between<D, R>(&self, range: R) -> u64
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        self.matcher().count_between(range)
+    }
+
+    /// Returns the `n`th DateTime, counting from zero, that matches the schedule after `after`.
+    ///
+    /// This is the same as `schedule.after(after).nth(n)`, but whole years, months and days
+    /// with one UTC offset are skipped using the number of matching days in them and the number
+    /// of hours, minutes and seconds in the schedule. Only the partial day after `after` and days
+    /// with a DST transition are scanned one match at a time.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let every_second = Schedule::from_str("* * * * * *").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).single(),
+    ///     every_second.nth_after(&start, 86_399)
+    /// );
+    /// ```
+    pub fn nth_after<D>(&self, after: &D, n: u64) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().nth_after(after, n)
+    }
+
+    /// Lists every time the schedule matches a wall clock time that a DST transition in
+    /// `timezone` skips or repeats, for the local dates within `dates`.
+    ///
+    /// The report covers what the schedule matches regardless of its [DstGapPolicy] and
+    /// [DstFoldPolicy], so it shows where those policies come into play. An unbounded end stops
+    /// at the first or last year the schedule can match.
+    /// # Example
+    /// ```
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    /// use chrono_tz::America::New_York;
+    /// use cron::{DstAnomaly, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 1,2 * * ?").unwrap();
+    /// let year = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()..NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    /// let anomalies = schedule.dst_anomalies(&New_York, year);
+    /// assert_eq!(2, anomalies.len());
+    ///
+    /// // 02:30 is skipped on March 10th. Read with the offsets from either side of the gap,
+    /// // it would be 01:30 EST or 03:30 EDT.
+    /// assert!(matches!(anomalies[0], DstAnomaly::Skipped { .. }));
+    /// assert_eq!("2024-03-10 02:30:00", anomalies[0].local().to_string());
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 3, 10, 6, 30, 0).unwrap(), *anomalies[0].earlier());
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 3, 10, 7, 30, 0).unwrap(), *anomalies[0].later());
+    ///
+    /// // 01:30 happens twice on November 3rd.
+    /// assert!(matches!(anomalies[1], DstAnomaly::Repeated { .. }));
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 11, 3, 5, 30, 0).unwrap(), *anomalies[1].earlier());
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 11, 3, 6, 30, 0).unwrap(), *anomalies[1].later());
+    /// ```
+    pub fn dst_anomalies<Z, R>(&self, timezone: &Z, dates: R) -> Vec<DstAnomaly<Z::DateTime>>
+    where
+        Z: ScheduleTimeZone,
+        R: RangeBounds<NaiveDate>,
+    {
+        self.matcher().dst_anomalies(timezone, dates)
+    }
+
+    /// Translates the schedule in `timezone` into UTC schedules for the local dates within
+    /// `dates`, for platforms that only run UTC cron expressions.
+    ///
+    /// The dates are split into periods with one UTC offset, each with its own UTC schedules and
+    /// validity range. The translation lists every UTC date on which the UTC schedules fire
+    /// differently from this one, such as when a fire moves into another month and its day of
+    /// the month can't be shifted with it. An unbounded end stops at the first or last year the
+    /// schedule can match.
+    /// # Example
+    /// ```
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    /// use chrono_tz::America::New_York;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 21 ? * Fri").unwrap();
+    /// let year = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()..=NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    /// let translation = schedule.to_utc(&New_York, year);
+    /// assert!(translation.is_exact());
+    ///
+    /// // 21:30 on Friday in New York is early on Saturday in UTC, an hour earlier in summer.
+    /// let sources: Vec<_> = translation.schedules().iter().map(|utc| utc.schedule().source()).collect();
+    /// assert_eq!(vec!["0 30 2 * * 7 *", "0 30 1 * * 7 *", "0 30 2 * * 7 *"], sources);
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 3, 10, 7, 0, 0).unwrap(), translation.schedules()[1].valid_from());
+    /// ```
+    pub fn to_utc<Z, R>(&self, timezone: &Z, dates: R) -> UtcTranslation
+    where
+        Z: ScheduleTimeZone,
+        R: RangeBounds<NaiveDate>,
+    {
+        utc_translation::translate(self, timezone, dates)
+    }
+
+    /// Returns true if the schedule fires at `date_time`, honouring the [DstGapPolicy] and
+    /// [DstFoldPolicy].
+    pub fn includes<D>(&self, date_time: D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        self.matcher().includes(date_time)
+    }
+
+    /// Returns true if the wall clock time `local` matches the schedule, without regard to any
+    /// time zone.
+    pub fn includes_naive(&self, local: NaiveDateTime) -> bool {
+        self.matcher().includes_naive(local)
+    }
+
+    /// Restricts this [Schedule] to the dates matched by an [AnchoredStep].
+    ///
+    /// The step is applied on top of the other date fields, so a step of two weeks combined with
+    /// a days of week field of `Tue` fires every other Tuesday. It's written into the
+    /// [Schedule::source] expression as a `step` option, like `0 0 9 ? * Tue; step=2w@2024-01-02`
+    /// with a unit of `d`, `w` or `m`.
+    pub fn with_anchored_step(mut self, step: AnchoredStep) -> Schedule {
+        self.fields.anchored_step = Some(step);
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::STEP,
+            Some(&parsing::anchored_step_text(&step)),
+        );
+        self
+    }
+
+    /// Returns the [AnchoredStep] restricting this [Schedule], if any.
+    pub fn anchored_step(&self) -> Option<&AnchoredStep> {
+        self.fields.anchored_step.as_ref()
+    }
+
+    /// Restricts this [Schedule] to the given ISO 8601 weeks of the year.
+    ///
+    /// The expression uses the same syntax as the other fields, so `*/2` or `odd` match odd
+    /// weeks and `2/2` or `even` match even weeks. Week 53 and the following week 1 are both odd.
+    /// The expression is written into the [Schedule::source] as a `weeks-of-year` option, like
+    /// `0 0 9 ? * Mon; weeks-of-year=even`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let on_call = Schedule::from_str("0 0 9 ? * Mon")
+    ///     .unwrap()
+    ///     .with_weeks_of_year("even")
+    ///     .unwrap();
+    /// let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    /// // 2026-01-05 is the Monday of ISO week 2.
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap(), on_call.after(&start).next().unwrap());
+    /// assert_eq!(on_call, Schedule::from_str("0 0 9 ? * Mon; weeks-of-year=even").unwrap());
+    /// ```
+    pub fn with_weeks_of_year(mut self, expression: &str) -> Result<Schedule, Error> {
+        self.fields.weeks_of_year = parse_field(expression)?;
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::WEEKS_OF_YEAR,
+            Some(expression.trim()).filter(|_| !self.fields.weeks_of_year.is_all()),
+        );
+        Ok(self)
+    }
+
+    /// Restricts this [Schedule] to the given ordinal days of the year, from 1 to 366.
+    ///
+    /// Ordinal days don't map onto fixed month and day pairs, so day 91 is April 1st in common
+    /// years and March 31st in leap years. The expression is written into the [Schedule::source]
+    /// as a `days-of-year` option, like `0 0 2 * * ?; days-of-year=1,91,182,274`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let batch = Schedule::from_str("0 0 2 * * ?")
+    ///     .unwrap()
+    ///     .with_days_of_year("1,91,182,274")
+    ///     .unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 3, 31, 2, 0, 0).unwrap(), batch.after(&start).next().unwrap());
+    /// ```
+    pub fn with_days_of_year(mut self, expression: &str) -> Result<Schedule, Error> {
+        self.fields.days_of_year = parse_field(expression)?;
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::DAYS_OF_YEAR,
+            Some(expression.trim()).filter(|_| !self.fields.days_of_year.is_all()),
+        );
+        Ok(self)
+    }
+
+    /// Restricts this [Schedule] to a single day of every quarter, like its first weekday.
+    ///
+    /// The day is resolved for each quarter during iteration. In an expression, the days of month
+    /// field can name a quarter day with the tokens `1Q`, `LQ`, `1WQ` and `LWQ`, for the first,
+    /// last, first weekday and last weekday of the quarter. When the field doesn't already name
+    /// it, the quarter day is written into the [Schedule::source] as a `quarter-day` option.
+    /// # Example
+    /// ```
+    /// use cron::{QuarterDay, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let written = Schedule::from_str("0 0 18 LQ Q4 ?").unwrap();
+    /// assert_eq!(Some(QuarterDay::Last), written.quarter_day());
+    ///
+    /// let set = Schedule::from_str("0 0 18 ? Q4 ?")
+    ///     .unwrap()
+    ///     .with_quarter_day(QuarterDay::Last);
+    /// assert_eq!("0 0 18 ? Q4 ?; quarter-day=LQ", set.source());
+    /// ```
+    pub fn with_quarter_day(mut self, quarter_day: QuarterDay) -> Schedule {
+        self.fields.quarter_day = Some(quarter_day);
+        let written = parsing::written_quarter_day(&self.source);
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::QUARTER_DAY,
+            Some(parsing::quarter_day_text(quarter_day)).filter(|_| written != Some(quarter_day)),
+        );
+        self
+    }
+
+    /// Returns the [QuarterDay] restricting this [Schedule], if any.
+    pub fn quarter_day(&self) -> Option<QuarterDay> {
+        self.fields.quarter_day
+    }
+
+    /// Sets how this [Schedule] treats days of the month that are past the end of a month.
+    ///
+    /// The policy applies to forward iteration, reverse iteration and [Schedule::includes]
+    /// alike. It's written into the [Schedule::source] expression as a `month-end` option.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::{MonthEndPolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let billing = Schedule::from_str("0 0 0 31 * ?")
+    ///     .unwrap()
+    ///     .with_month_end_policy(MonthEndPolicy::Clamp);
+    /// let start = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 4, 30, 0, 0, 0).unwrap(), billing.after(&start).next().unwrap());
+    /// assert_eq!("0 0 0 31 * ?; month-end=clamp", billing.source());
+    /// ```
+    pub fn with_month_end_policy(mut self, policy: MonthEndPolicy) -> Schedule {
+        self.fields.month_end_policy = policy;
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::MONTH_END,
+            parsing::option_text(&parsing::MONTH_END_POLICIES, &policy),
+        );
+        self
+    }
+
+    /// Returns the [MonthEndPolicy] used by this [Schedule].
+    pub fn month_end_policy(&self) -> MonthEndPolicy {
+        self.fields.month_end_policy
+    }
+
+    /// Sets how this [Schedule] treats matching wall clock times that are skipped when the
+    /// clocks spring forward.
+    ///
+    /// The policy applies to forward iteration, reverse iteration and [Schedule::includes]
+    /// alike. It's written into the [Schedule::source] expression as an option after the fields,
+    /// like `0 30 2 * * ?; dst-gap=shift-forward`, so it survives parsing and serialization.
+    /// # Example
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::America::New_York;
+    /// use cron::{DstGapPolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let nightly = Schedule::from_str("0 30 2 * * ?")
+    ///     .unwrap()
+    ///     .with_dst_gap_policy(DstGapPolicy::ShiftForward);
+    /// let start = New_York.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+    /// // 02:30 doesn't exist on the 10th, so the job runs as soon as the clocks reach 03:00.
+    /// assert_eq!(New_York.with_ymd_and_hms(2024, 3, 10, 3, 0, 0).unwrap(), nightly.after(&start).next().unwrap());
+    /// assert_eq!("0 30 2 * * ?; dst-gap=shift-forward", nightly.source());
+    /// ```
+    pub fn with_dst_gap_policy(mut self, policy: DstGapPolicy) -> Schedule {
+        self.fields.dst_gap_policy = policy;
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::DST_GAP,
+            parsing::option_text(&parsing::DST_GAP_POLICIES, &policy),
+        );
+        self
+    }
+
+    /// Returns the [DstGapPolicy] used by this [Schedule].
+    pub fn dst_gap_policy(&self) -> DstGapPolicy {
+        self.fields.dst_gap_policy
+    }
+
+    /// Sets which of the instants this [Schedule] fires at when a matching wall clock time
+    /// happens twice because the clocks fall back.
+    ///
+    /// The policy applies to forward iteration, reverse iteration and [Schedule::includes]
+    /// alike. It's written into the [Schedule::source] expression as a `dst-fold` option, with
+    /// the values `both`, `earlier-only` and `later-only`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::America::New_York;
+    /// use cron::{DstFoldPolicy, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let nightly = Schedule::from_str("0 30 1 * * ?")
+    ///     .unwrap()
+    ///     .with_dst_fold_policy(DstFoldPolicy::EarlierOnly);
+    /// let start = New_York.with_ymd_and_hms(2024, 11, 3, 0, 0, 0).unwrap();
+    /// let mut fires = nightly.after(&start);
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 11, 3, 5, 30, 0).unwrap(), fires.next().unwrap());
+    /// assert_eq!(Utc.with_ymd_and_hms(2024, 11, 4, 6, 30, 0).unwrap(), fires.next().unwrap());
+    /// ```
+    pub fn with_dst_fold_policy(mut self, policy: DstFoldPolicy) -> Schedule {
+        self.fields.dst_fold_policy = policy;
+        self.source = parsing::with_option(
+            &self.source,
+            parsing::DST_FOLD,
+            parsing::option_text(&parsing::DST_FOLD_POLICIES, &policy),
+        );
+        self
+    }
+
+    /// Returns the [DstFoldPolicy] used by this [Schedule].
+    pub fn dst_fold_policy(&self) -> DstFoldPolicy {
+        self.fields.dst_fold_policy
+    }
+
+    /// Applies the option `name` from the end of an expression through its setter.
+    pub(crate) fn with_option(self, name: &str, value: &str) -> Result<Schedule, Error> {
+        match name {
+            parsing::STEP => Ok(self.with_anchored_step(parsing::anchored_step(value)?)),
+            parsing::WEEKS_OF_YEAR => self.with_weeks_of_year(value),
+            parsing::DAYS_OF_YEAR => self.with_days_of_year(value),
+            parsing::QUARTER_DAY => Ok(self.with_quarter_day(parsing::option_value(
+                &parsing::QUARTER_DAYS,
+                name,
+                value,
+            )?)),
+            parsing::MONTH_END => Ok(self.with_month_end_policy(parsing::option_value(
+                &parsing::MONTH_END_POLICIES,
+                name,
+                value,
+            )?)),
+            parsing::DST_GAP => Ok(self.with_dst_gap_policy(parsing::option_value(
+                &parsing::DST_GAP_POLICIES,
+                name,
+                value,
+            )?)),
+            parsing::DST_FOLD => Ok(self.with_dst_fold_policy(parsing::option_value(
+                &parsing::DST_FOLD_POLICIES,
+                name,
+                value,
+            )?)),
+            _ => Err(ErrorKind::Expression(format!("'{name}' is not a valid option.")).into()),
+        }
+    }
+
+    /// Replaces the source expression, keeping the fields it was parsed into.
+    pub(crate) fn with_source(mut self, source: String) -> Schedule {
+        self.source = source;
+        self
+    }
+
+    /// Returns the days of a month matched by all of the date fields, one bit per day.
+    pub(crate) fn day_mask(&self, year: Ordinal, month: Ordinal) -> u32 {
+        self.fields.day_mask(year, month)
+    }
+
+    fn matcher(&self) -> Matcher<'_> {
+        Matcher::new(self, None)
+    }
+
+    /// Returns the first second after `local` that the schedule doesn't match, in wall clock
+    /// time, where `local` is a time it matches. Minutes, hours and days are skipped whole when
+    /// the schedule matches every second of them.
+    pub(crate) fn naive_run_end(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let fields = &self.fields;
+        let mut cursor = local.with_nanosecond(0)?;
+        loop {
+            let date = cursor.date();
+            let next = if !fields.seconds.is_all() {
+                if let Some(second) = first_gap_after(&fields.seconds, cursor.second()) {
+                    return cursor.with_second(second);
+                }
+                date.and_hms_opt(cursor.hour(), cursor.minute(), 0)? + Duration::minutes(1)
+            } else if !fields.minutes.is_all() {
+                if let Some(minute) = first_gap_after(&fields.minutes, cursor.minute()) {
+                    return date.and_hms_opt(cursor.hour(), minute, 0);
+                }
+                date.and_hms_opt(cursor.hour(), 0, 0)? + Duration::hours(1)
+            } else {
+                if !fields.hours.is_all() {
+                    if let Some(hour) = first_gap_after(&fields.hours, cursor.hour()) {
+                        return date.and_hms_opt(hour, 0, 0);
+                    }
+                }
+                date.succ_opt()?.and_hms_opt(0, 0, 0)?
+            };
+            if !self.includes_naive(next) {
+                return Some(next);
+            }
+            cursor = next;
+        }
+    }
+
+    /// Returns the first second of the run of seconds the schedule matches up to `local`, in
+    /// wall clock time, where `local` is a time it matches. This mirrors `naive_run_end`.
+    pub(crate) fn naive_run_start(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let fields = &self.fields;
+        let mut cursor = local.with_nanosecond(0)?;
+        loop {
+            let date = cursor.date();
+            let start = if !fields.seconds.is_all() {
+                if let Some(second) = last_gap_before(&fields.seconds, cursor.second()) {
+                    return cursor.with_second(second + 1);
+                }
+                date.and_hms_opt(cursor.hour(), cursor.minute(), 0)?
+            } else if !fields.minutes.is_all() {
+                if let Some(minute) = last_gap_before(&fields.minutes, cursor.minute()) {
+                    return date.and_hms_opt(cursor.hour(), minute + 1, 0);
+                }
+                date.and_hms_opt(cursor.hour(), 0, 0)?
+            } else {
+                if !fields.hours.is_all() {
+                    if let Some(hour) = last_gap_before(&fields.hours, cursor.hour()) {
+                        return date.and_hms_opt(hour + 1, 0, 0);
+                    }
+                }
+                date.and_hms_opt(0, 0, 0)?
+            };
+            let previous = start - Duration::seconds(1);
+            if !self.includes_naive(previous) {
+                return Some(start);
+            }
+            cursor = previous;
+        }
+    }
+
+    /// Identifies the fields that decide which days match, so schedules that only differ in
+    /// their times can share compiled day masks.
+    #[cfg(feature = "std")]
+    pub(crate) fn date_key(&self) -> impl Hash + Eq + '_ {
+        self.fields.date_key()
+    }
+
+    /// Returns a schedule that fires when both this schedule and `other` do, or `None` if
+    /// either uses options beyond the fields and the week and day of year filters, which can't
+    /// be combined field by field.
+    pub(crate) fn intersection(&self, other: &Schedule) -> Option<Schedule> {
+        let fields = self.fields.intersection(&other.fields)?;
+        Some(Schedule::new(
+            format!("{} & {}", self.source, other.source),
+            fields,
+        ))
+    }
+
+    /// Returns a [TimeUnitSpec] describing the years included in this [Schedule].
+    pub fn years(&self) -> &impl TimeUnitSpec {
+        &self.fields.years
+    }
+
+    /// Returns a [TimeUnitSpec] describing the months of the year included in this [Schedule].
+    pub fn months(&self) -> &impl TimeUnitSpec {
+        &self.fields.months
+    }
+
+    /// Returns a [TimeUnitSpec] describing the days of the month included in this [Schedule].
+    pub fn days_of_month(&self) -> &impl TimeUnitSpec {
+        &self.fields.days_of_month
+    }
+
+    /// Returns a [TimeUnitSpec] describing the days of the week included in this [Schedule].
+    pub fn days_of_week(&self) -> &impl TimeUnitSpec {
+        &self.fields.days_of_week
+    }
+
+    /// Returns a [TimeUnitSpec] describing the ordinal days of the year included in this
+    /// [Schedule].
+    pub fn days_of_year(&self) -> &impl TimeUnitSpec {
+        &self.fields.days_of_year
+    }
+
+    /// Returns a [TimeUnitSpec] describing the ISO 8601 weeks of the year included in this
+    /// [Schedule].
+    pub fn weeks_of_year(&self) -> &impl TimeUnitSpec {
+        &self.fields.weeks_of_year
+    }
+
+    /// Returns a [TimeUnitSpec] describing the hours of the day included in this [Schedule].
+    pub fn hours(&self) -> &impl TimeUnitSpec {
+        &self.fields.hours
+    }
+
+    /// Returns a [TimeUnitSpec] describing the minutes of the hour included in this [Schedule].
+    pub fn minutes(&self) -> &impl TimeUnitSpec {
+        &self.fields.minutes
+    }
+
+    /// Returns a [TimeUnitSpec] describing the seconds of the minute included in this [Schedule].
+    pub fn seconds(&self) -> &impl TimeUnitSpec {
+        &self.fields.seconds
+    }
+
+    pub fn timeunitspec_eq(&self, other: &Schedule) -> bool {
+        self.fields == other.fields
+    }
+
+    /// Returns a reference to the source cron expression.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl PartialEq for Schedule {
+    fn eq(&self, other: &Schedule) -> bool {
+        self.source == other.source && self.fields == other.fields
+    }
+}
+
+/// How a [Schedule] treats days of the month that don't exist in a given month, like the 31st
+/// of April or the 29th of February in common years.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MonthEndPolicy {
+    /// Months without the day are skipped. This is the default.
+    #[default]
+    Skip,
+    /// The day is clamped to the last day of months that are too short for it, so the 31st
+    /// fires on April 30th and the 29th fires on February 28th in common years.
+    Clamp,
+}
+
+/// How a [Schedule] treats matching wall clock times that don't exist because the clocks spring
+/// forward over them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DstGapPolicy {
+    /// Times in the gap are skipped. This is the default.
+    #[default]
+    Skip,
+    /// The schedule fires once at the end of the gap if any time in it matches, the way Vixie
+    /// cron and systemd run jobs from a skipped hour.
+    ShiftForward,
+}
+
+/// Which instants a [Schedule] fires at for matching wall clock times that happen twice because
+/// the clocks fall back over them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DstFoldPolicy {
+    /// The schedule fires at both instants. This is the default.
+    #[default]
+    Both,
+    /// The schedule only fires the first time, before the clocks fall back.
+    EarlierOnly,
+    /// The schedule only fires the second time, after the clocks fall back.
+    LaterOnly,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduleFields {
+    years: Years,
+    days_of_week: DaysOfWeek,
+    months: Months,
+    days_of_month: DaysOfMonth,
+    hours: Hours,
+    minutes: Minutes,
+    seconds: Seconds,
+    weeks_of_year: WeeksOfYear,
+    days_of_year: DaysOfYear,
+    quarter_day: Option<QuarterDay>,
+    month_end_policy: MonthEndPolicy,
+    dst_gap_policy: DstGapPolicy,
+    dst_fold_policy: DstFoldPolicy,
+    anchored_step: Option<AnchoredStep>,
+}
+
+impl ScheduleFields {
+    pub(crate) fn new(
+        seconds: Seconds,
+        minutes: Minutes,
+        hours: Hours,
+        days_of_month: DaysOfMonth,
+        months: Months,
+        days_of_week: DaysOfWeek,
+        years: Years,
+    ) -> ScheduleFields {
+        ScheduleFields {
+            years,
+            days_of_week,
+            months,
+            days_of_month,
+            hours,
+            minutes,
+            seconds,
+            weeks_of_year: WeeksOfYear::all(),
+            days_of_year: DaysOfYear::all(),
+            quarter_day: None,
+            month_end_policy: MonthEndPolicy::Skip,
+            dst_gap_policy: DstGapPolicy::Skip,
+            dst_fold_policy: DstFoldPolicy::Both,
+            anchored_step: None,
+        }
+    }
+
+    /// Restricts the fields to the [QuarterDay] named in the days of month field, if any.
+    pub(crate) fn with_quarter_day(mut self, quarter_day: Option<QuarterDay>) -> ScheduleFields {
+        self.quarter_day = quarter_day;
+        self
+    }
+
+    #[cfg(feature = "std")]
+    fn date_key(&self) -> impl Hash + Eq + '_ {
+        (
+            (
+                self.years.ordinals(),
+                self.months.ordinals(),
+                self.days_of_month.ordinals(),
+                self.days_of_week.ordinals(),
+                self.weeks_of_year.ordinals(),
+                self.days_of_year.ordinals(),
+            ),
+            self.quarter_day,
+            self.month_end_policy,
+            self.anchored_step,
+        )
+    }
+
+    fn intersection(&self, other: &ScheduleFields) -> Option<ScheduleFields> {
+        fn both<T: TimeUnitField>(field: &T, other: &T) -> T {
+            let ordinals = field.ordinals();
+            let other = other.ordinals();
+            T::from_ordinal_set(ordinals.iter().filter(|o| other.contains(o)).collect())
+        }
+        let combinable = |fields: &ScheduleFields| {
+            fields.quarter_day.is_none()
+                && fields.anchored_step.is_none()
+                && fields.month_end_policy == MonthEndPolicy::Skip
+                && fields.dst_gap_policy == DstGapPolicy::Skip
+                && fields.dst_fold_policy == DstFoldPolicy::Both
+        };
+        if !combinable(self) || !combinable(other) {
+            return None;
+        }
+        let mut fields = ScheduleFields::new(
+            both(&self.seconds, &other.seconds),
+            both(&self.minutes, &other.minutes),
+            both(&self.hours, &other.hours),
+            both(&self.days_of_month, &other.days_of_month),
+            both(&self.months, &other.months),
+            both(&self.days_of_week, &other.days_of_week),
+            both(&self.years, &other.years),
+        );
+        fields.weeks_of_year = both(&self.weeks_of_year, &other.weeks_of_year);
+        fields.days_of_year = both(&self.days_of_year, &other.days_of_year);
+        Some(fields)
+    }
+
+    /// Finds the instants a matching wall clock time fires at, honouring the [DstGapPolicy] and
+    /// [DstFoldPolicy].
+    fn resolve_local<Z>(&self, timezone: &Z, local: &NaiveDateTime) -> LocalResult<Z::DateTime>
+    where
+        Z: ScheduleTimeZone,
+    {
+        match (
+            timezone.at_local(local),
+            self.dst_gap_policy,
+            self.dst_fold_policy,
+        ) {
+            (LocalResult::None, DstGapPolicy::ShiftForward, _) => {
+                dst_gap_end(timezone, local).map_or(LocalResult::None, LocalResult::Single)
+            }
+            (LocalResult::Ambiguous(earlier, _), _, DstFoldPolicy::EarlierOnly) => {
+                LocalResult::Single(earlier)
+            }
+            (LocalResult::Ambiguous(_, later), _, DstFoldPolicy::LaterOnly) => {
+                LocalResult::Single(later)
+            }
+            (local_result, _, _) => local_result,
+        }
+    }
+
+    /// Checks the days of month field, honouring the [MonthEndPolicy].
+    fn includes_day_of_month(&self, year: Ordinal, month: Ordinal, day: Ordinal) -> bool {
+        if self.days_of_month.includes(day) {
+            return true;
+        }
+        let last_day = days_in_month(month, year);
+        self.month_end_policy == MonthEndPolicy::Clamp
+            && day == last_day
+            && self
+                .days_of_month
+                .range((Excluded(last_day), Unbounded))
+                .next()
+                .is_some()
+    }
+
+    /// Returns the days between `first` and `last` inclusive that are matched by all of the date
+    /// fields, in ascending order.
+    fn days_of_month_in(
+        &self,
+        year: Ordinal,
+        month: Ordinal,
+        first: Ordinal,
+        last: Ordinal,
+    ) -> impl DoubleEndedIterator<Item = Ordinal> + '_ {
+        let last_day = days_in_month(month, year);
+        let last = last.min(last_day);
+        let first = first.min(last);
+        // A clamped day is never in the days of month field itself, so it comes after the
+        // ordinals in range.
+        let clamped_day = Some(last_day).filter(|&day| {
+            day == last
+                && !self.days_of_month.includes(day)
+                && self.includes_day_of_month(year, month, day)
+        });
+        self.days_of_month
+            .ordinals()
+            .range((Included(first), Included(last)))
+            .chain(clamped_day)
+            .filter(move |&day| self.includes_date(year, month, day))
+    }
+
+    /// Returns the days of a month matched by all of the date fields, one bit per day.
+    ///
+    /// The days of month and days of week fields are combined as masks. Only the optional date
+    /// filters need each day checked.
+    fn day_mask(&self, year: Ordinal, month: Ordinal) -> u32 {
+        if !self.years.includes(year) || !self.months.includes(month) {
+            return 0;
+        }
+        let Some(first) = NaiveDate::from_ymd_opt(year as i32, month, 1) else {
+            return 0;
+        };
+        let last_day = days_in_month(month, year);
+        let mut mask = self
+            .days_of_month
+            .range(..=last_day)
+            .fold(0, |mask, day| mask | 1 << day);
+        if self.includes_day_of_month(year, month, last_day) {
+            mask |= 1 << last_day;
+        }
+        if !self.days_of_week.is_all() {
+            // Bits 1 to 7 hold the first week of the month, which repeats every seven days.
+            let first_weekday = first.weekday().num_days_from_sunday();
+            let week = (1..=7).fold(0u64, |week, day| {
+                let weekday = (first_weekday + day - 1) % 7 + 1;
+                week | (self.days_of_week.includes(weekday) as u64) << day
+            });
+            mask &= (week | week << 7 | week << 14 | week << 21 | week << 28) as u32;
+        }
+        if !(self.weeks_of_year.is_all()
+            && self.days_of_year.is_all()
+            && self.quarter_day.is_none()
+            && self.anchored_step.is_none())
+        {
+            mask = MaskDays::new(mask, 1, 31)
+                .filter(|&day| self.includes_date(year, month, day))
+                .fold(0, |mask, day| mask | 1 << day);
+        }
+        mask
+    }
+
+    /// Returns true if a day is matched by all of the date fields other than years and months.
+    fn includes_day(&self, year: Ordinal, month: Ordinal, day: Ordinal) -> bool {
+        self.includes_day_of_month(year, month, day) && self.includes_date(year, month, day)
+    }
+
+    /// Checks the filters that need the full date rather than a single ordinal.
+    fn includes_date(&self, year: Ordinal, month: Ordinal, day: Ordinal) -> bool {
+        if self.days_of_week.is_all()
+            && self.weeks_of_year.is_all()
+            && self.days_of_year.is_all()
+            && self.quarter_day.is_none()
+            && self.anchored_step.is_none()
+        {
+            return true;
+        }
+        let Some(date) = NaiveDate::from_ymd_opt(year as i32, month, day) else {
+            return false;
+        };
+        self.days_of_week
+            .includes(date.weekday().number_from_sunday())
+            && self.weeks_of_year.includes(date.iso_week().week())
+            && self.days_of_year.includes(date.ordinal())
+            && self
+                .quarter_day
+                .as_ref()
+                .is_none_or(|quarter_day| quarter_day.includes(date))
+            && self
+                .anchored_step
+                .as_ref()
+                .is_none_or(|step| step.includes(date))
+    }
+}
+
+/// The fields of a [Schedule] along with the tables compiled for them by [CompiledSchedule], if
+/// any. All of the queries are answered here, so compiled and uncompiled schedules share them.
+///
+/// [CompiledSchedule]: crate::CompiledSchedule
+#[derive(Clone, Copy)]
+pub(crate) struct Matcher<'a> {
+    fields: &'a ScheduleFields,
+    tables: Option<&'a Tables>,
+}
+
+impl<'a> Matcher<'a> {
+    pub(crate) fn new(schedule: &'a Schedule, tables: Option<&'a Tables>) -> Matcher<'a> {
+        Matcher {
+            fields: &schedule.fields,
+            tables,
+        }
+    }
+
+    pub(crate) fn next_within<D>(&self, after: &D, until: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        // Every candidate is resolved in this zone, so it's only looked up once.
+        let timezone = after.time_zone();
+        // Stopping at the end of the range keeps bounded searches from scanning every year up
+        // to the end of the schedule.
+        let year_upper_bound = match until {
+            Included(end) if end < after => return None,
+            Excluded(end) if end <= after => return None,
+            Included(end) | Excluded(end) => Included(year_ordinal(
+                end.with_time_zone(&timezone).naive_local().year(),
+            )),
+            Unbounded => Unbounded,
+        };
         let mut query = NextAfterQuery::from(after);
         // Ambiguous naive datetimes translate to two local datetimes. This
         // iteration uses naive datetimes and could skip the second local
@@ -44,14 +1114,14 @@ impl Schedule {
         // candidate retains that datetime for consideration on subsequent
         // iterations. For example, during fall back, `0 0/30 * * * * *` must
         // emit both 01:30 local datetimes.
-        let mut deferred_candidate: Option<DateTime<Z>> = None;
+        let mut deferred_candidate: Option<D> = None;
         // Folded time happens during the fall back DST transition where an
         // offset, typically one hour, is repeated in both timezones. This flag
         // tells iteration it is starting in the first repeated offset so it
         // does not get stuck repeating matches from the first fold or skip
         // matches in the second fold.
         let after_naive = after.naive_local();
-        let after_in_first_fold = match after.timezone().from_local_datetime(&after_naive) {
+        let after_in_first_fold = match timezone.at_local(&after_naive) {
             LocalResult::Ambiguous(first, second) => {
                 let earlier = min(first, second);
                 *after == earlier
@@ -62,11 +1132,10 @@ impl Schedule {
             .fields
             .years
             .ordinals()
-            .range((Included(query.year_lower_bound()), Unbounded))
-            .cloned()
+            .range((Included(query.year_lower_bound()), year_upper_bound))
         {
             // It's a future year, the current year's range is irrelevant.
-            if year > after.year() as u32 {
+            if year > year_ordinal(after_naive.year()) {
                 query.reset_month();
             }
             let month_start = query.month_lower_bound();
@@ -74,51 +1143,35 @@ impl Schedule {
                 query.reset_month();
             }
             let month_range = (Included(month_start), Included(Months::inclusive_max()));
-            for month in self.fields.months.ordinals().range(month_range).cloned() {
+            for month in self.fields.months.ordinals().range(month_range) {
                 let day_of_month_start = query.day_of_month_lower_bound();
                 if !self
                     .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_start)
+                    .includes_day_of_month(year, month, day_of_month_start)
                 {
                     query.reset_day_of_month();
                 }
                 let day_of_month_end = days_in_month(month, year);
-                let day_of_month_range = (
-                    Included(day_of_month_start.min(day_of_month_end)),
-                    Included(day_of_month_end),
-                );
 
                 let mut day_iter = self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
-                    .cloned()
-                    .filter(|&day| {
-                        self.fields.days_of_week.is_all()
-                            || NaiveDate::from_ymd_opt(year as i32, month, day)
-                                .map(|d| {
-                                    self.fields
-                                        .days_of_week
-                                        .ordinals()
-                                        .contains(&d.weekday().number_from_sunday())
-                                })
-                                .unwrap_or(false)
-                    })
+                    .days_of_month_in(
+                        year,
+                        month,
+                        day_of_month_start.min(day_of_month_end),
+                        day_of_month_end,
+                    )
                     .peekable();
                 if day_iter.peek() != Some(&day_of_month_start) {
                     query.reset_day_of_month();
                 }
                 for day_of_month in day_iter {
                     let hour_start = query.hour_lower_bound();
-                    if !self.fields.hours.ordinals().contains(&hour_start) {
+                    if !self.includes_hour(hour_start) {
                         query.reset_hour();
                     }
                     let hour_range = (Included(hour_start), Included(Hours::inclusive_max()));
 
-                    for hour in self.fields.hours.ordinals().range(hour_range).cloned() {
+                    for hour in self.fields.hours.ordinals().range(hour_range) {
                         // The first fold is the first repeat of the DST offset,
                         // typically one hour. Because this iteration is done in
                         // naive time, it can skip matches after finding one in
@@ -135,37 +1188,32 @@ impl Schedule {
                         } else {
                             query_minute_start
                         };
-                        if !self.fields.minutes.ordinals().contains(&minute_start) {
+                        if !self.includes_minute(minute_start) {
                             query.reset_minute();
                         }
                         let minute_range =
                             (Included(minute_start), Included(Minutes::inclusive_max()));
 
-                        for minute in self.fields.minutes.ordinals().range(minute_range).cloned() {
+                        for minute in self.fields.minutes.ordinals().range(minute_range) {
                             let query_second_start = query.second_lower_bound();
                             let second_start = if fold_hour_scan {
                                 Seconds::inclusive_min()
                             } else {
                                 query_second_start
                             };
-                            if !self.fields.seconds.ordinals().contains(&second_start) {
+                            if !self.includes_second(second_start) {
                                 query.reset_second();
                             }
                             let second_range =
                                 (Included(second_start), Included(Seconds::inclusive_max()));
 
-                            for second in
-                                self.fields.seconds.ordinals().range(second_range).cloned()
-                            {
-                                let timezone = after.timezone();
-                                let local_result = timezone.with_ymd_and_hms(
-                                    year as i32,
-                                    month,
-                                    day_of_month,
-                                    hour,
-                                    minute,
-                                    second,
-                                );
+                            for second in self.fields.seconds.ordinals().range(second_range) {
+                                let Some(local) =
+                                    local_ymd_hms(year, month, day_of_month, hour, minute, second)
+                                else {
+                                    continue;
+                                };
+                                let local_result = self.fields.resolve_local(&timezone, &local);
                                 match local_result {
                                     LocalResult::None => continue,
                                     LocalResult::Single(candidate) => {
@@ -173,16 +1221,19 @@ impl Schedule {
                                             continue;
                                         }
                                         if let Some(deferred) = deferred_candidate.take() {
-                                            return Some(min(deferred, candidate));
+                                            return bounded_above(min(deferred, candidate), until);
                                         }
-                                        return Some(candidate);
+                                        return bounded_above(candidate, until);
                                     }
                                     LocalResult::Ambiguous(earlier, later) => {
                                         if earlier > *after {
                                             if let Some(deferred) = deferred_candidate.take() {
-                                                return Some(min(deferred, earlier));
+                                                return bounded_above(
+                                                    min(deferred, earlier),
+                                                    until,
+                                                );
                                             }
-                                            return Some(earlier);
+                                            return bounded_above(earlier, until);
                                         }
                                         if later > *after {
                                             deferred_candidate = Some(match deferred_candidate {
@@ -204,25 +1255,35 @@ impl Schedule {
         }
 
         if let Some(candidate) = deferred_candidate {
-            return Some(candidate);
+            return bounded_above(candidate, until);
         }
         // We ran out of dates to try.
         None
     }
 
-    fn prev_from<Z>(&self, before: &DateTime<Z>) -> Option<DateTime<Z>>
+    pub(crate) fn prev_within<D>(&self, before: &D, since: Bound<&D>) -> Option<D>
     where
-        Z: TimeZone,
+        D: ScheduleDateTime,
     {
+        // See `next_within` for looking up the zone once and stopping at the end of the range.
+        let timezone = before.time_zone();
+        let year_lower_bound = match since {
+            Included(start) if start > before => return None,
+            Excluded(start) if start >= before => return None,
+            Included(start) | Excluded(start) => Included(year_ordinal(
+                start.with_time_zone(&timezone).naive_local().year(),
+            )),
+            Unbounded => Unbounded,
+        };
         let mut query = PrevFromQuery::from(before);
-        // See `next_after` for folded-time details. This is the reverse scan's
+        // See `next_within` for folded-time details. This is the reverse scan's
         // deferred candidate for an earlier local datetime that may still be
         // the previous chronological match.
-        let mut deferred_candidate: Option<DateTime<Z>> = None;
-        // See `next_after` for folded-time details. This flag handles starting
+        let mut deferred_candidate: Option<D> = None;
+        // See `next_within` for folded-time details. This flag handles starting
         // from the second fold while scanning backward.
         let before_naive = before.naive_local();
-        let before_in_second_fold = match before.timezone().from_local_datetime(&before_naive) {
+        let before_in_second_fold = match timezone.at_local(&before_naive) {
             LocalResult::Ambiguous(first, second) => {
                 let later = max(first, second);
                 *before == later
@@ -233,9 +1294,8 @@ impl Schedule {
             .fields
             .years
             .ordinals()
-            .range((Unbounded, Included(query.year_upper_bound())))
+            .range((year_lower_bound, Included(query.year_upper_bound())))
             .rev()
-            .cloned()
         {
             let month_start = query.month_upper_bound();
 
@@ -244,68 +1304,32 @@ impl Schedule {
             }
             let month_range = (Included(Months::inclusive_min()), Included(month_start));
 
-            for month in self
-                .fields
-                .months
-                .ordinals()
-                .range(month_range)
-                .rev()
-                .cloned()
-            {
+            for month in self.fields.months.ordinals().range(month_range).rev() {
                 let day_of_month_end = query.day_of_month_upper_bound();
                 if !self
                     .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_end)
+                    .includes_day_of_month(year, month, day_of_month_end)
                 {
                     query.reset_day_of_month();
                 }
 
                 let day_of_month_end = days_in_month(month, year).min(day_of_month_end);
 
-                let day_of_month_range = (
-                    Included(DaysOfMonth::inclusive_min()),
-                    Included(day_of_month_end),
-                );
-
                 let mut day_iter = self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
+                    .days_of_month_in(year, month, DaysOfMonth::inclusive_min(), day_of_month_end)
                     .rev()
-                    .cloned()
-                    .filter(|&day| {
-                        self.fields.days_of_week.is_all()
-                            || NaiveDate::from_ymd_opt(year as i32, month, day)
-                                .map(|d| {
-                                    self.fields
-                                        .days_of_week
-                                        .ordinals()
-                                        .contains(&d.weekday().number_from_sunday())
-                                })
-                                .unwrap_or(false)
-                    })
                     .peekable();
                 if day_iter.peek() != Some(&day_of_month_end) {
                     query.reset_day_of_month();
                 }
                 for day_of_month in day_iter {
                     let hour_start = query.hour_upper_bound();
-                    if !self.fields.hours.ordinals().contains(&hour_start) {
+                    if !self.includes_hour(hour_start) {
                         query.reset_hour();
                     }
                     let hour_range = (Included(Hours::inclusive_min()), Included(hour_start));
 
-                    for hour in self
-                        .fields
-                        .hours
-                        .ordinals()
-                        .range(hour_range)
-                        .rev()
-                        .cloned()
-                    {
+                    for hour in self.fields.hours.ordinals().range(hour_range).rev() {
                         // See the forward `fold_hour_scan` for folded-time
                         // details. This is the reverse scan of the repeated
                         // hour from the second fold into the first.
@@ -320,49 +1344,32 @@ impl Schedule {
                         } else {
                             query_minute_start
                         };
-                        if !self.fields.minutes.ordinals().contains(&minute_start) {
+                        if !self.includes_minute(minute_start) {
                             query.reset_minute();
                         }
                         let minute_range =
                             (Included(Minutes::inclusive_min()), Included(minute_start));
 
-                        for minute in self
-                            .fields
-                            .minutes
-                            .ordinals()
-                            .range(minute_range)
-                            .rev()
-                            .cloned()
-                        {
+                        for minute in self.fields.minutes.ordinals().range(minute_range).rev() {
                             let query_second_start = query.second_upper_bound();
                             let second_start = if fold_hour_scan {
                                 Seconds::inclusive_max()
                             } else {
                                 query_second_start
                             };
-                            if !self.fields.seconds.ordinals().contains(&second_start) {
+                            if !self.includes_second(second_start) {
                                 query.reset_second();
                             }
                             let second_range =
                                 (Included(Seconds::inclusive_min()), Included(second_start));
 
-                            for second in self
-                                .fields
-                                .seconds
-                                .ordinals()
-                                .range(second_range)
-                                .rev()
-                                .cloned()
-                            {
-                                let timezone = before.timezone();
-                                let local_result = timezone.with_ymd_and_hms(
-                                    year as i32,
-                                    month,
-                                    day_of_month,
-                                    hour,
-                                    minute,
-                                    second,
-                                );
+                            for second in self.fields.seconds.ordinals().range(second_range).rev() {
+                                let Some(local) =
+                                    local_ymd_hms(year, month, day_of_month, hour, minute, second)
+                                else {
+                                    continue;
+                                };
+                                let local_result = self.fields.resolve_local(&timezone, &local);
                                 match local_result {
                                     LocalResult::None => continue,
                                     LocalResult::Single(candidate) => {
@@ -370,16 +1377,16 @@ impl Schedule {
                                             continue;
                                         }
                                         if let Some(deferred) = deferred_candidate.take() {
-                                            return Some(max(deferred, candidate));
+                                            return bounded_below(max(deferred, candidate), since);
                                         }
-                                        return Some(candidate);
+                                        return bounded_below(candidate, since);
                                     }
                                     LocalResult::Ambiguous(earlier, later) => {
                                         if later < *before {
                                             if let Some(deferred) = deferred_candidate.take() {
-                                                return Some(max(deferred, later));
+                                                return bounded_below(max(deferred, later), since);
                                             }
-                                            return Some(later);
+                                            return bounded_below(later, since);
                                         }
                                         if earlier < *before {
                                             deferred_candidate = Some(match deferred_candidate {
@@ -401,179 +1408,464 @@ impl Schedule {
         }
 
         if let Some(candidate) = deferred_candidate {
-            return Some(candidate);
+            return bounded_below(candidate, since);
         }
         // We ran out of dates to try.
         None
     }
 
-    /// Provides an iterator which will return each DateTime that matches the schedule starting with
-    /// the current time if applicable.
-    pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleIterator<'_, Z>
+    pub(crate) fn next_at_or_after<D>(&self, date_time: &D) -> Option<D>
     where
-        Z: TimeZone,
+        D: ScheduleDateTime,
     {
-        self.after(&timezone.from_utc_datetime(&Utc::now().naive_utc()))
+        self.next_within(
+            &date_time.checked_add_signed(-Duration::nanoseconds(1))?,
+            Unbounded,
+        )
     }
 
-    /// The same, but with an iterator with a static ownership
-    pub fn upcoming_owned<Z: TimeZone>(&self, timezone: Z) -> OwnedScheduleIterator<Z> {
-        self.after_owned(timezone.from_utc_datetime(&Utc::now().naive_utc()))
+    pub(crate) fn prev_at_or_before<D>(&self, date_time: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.prev_within(
+            &date_time.checked_add_signed(Duration::nanoseconds(1))?,
+            Unbounded,
+        )
     }
 
-    /// Like the `upcoming` method, but allows you to specify a start time other than the present.
-    pub fn after<Z>(&self, after: &DateTime<Z>) -> ScheduleIterator<'_, Z>
+    pub(crate) fn count_between<D, R>(&self, range: R) -> u64
     where
-        Z: TimeZone,
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
     {
-        ScheduleIterator::new(self, after)
+        let range: ScheduleRangeIterator<'_, D::TimeZone> = ScheduleRangeIterator::new(
+            *self,
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        );
+        let (Some(after), Some(before)) = (range.before_start(), range.after_end()) else {
+            return 0;
+        };
+        if before <= after {
+            return 0;
+        }
+        let timezone = after.time_zone();
+        let first_date = after.naive_local().date();
+        let last_date = before.with_time_zone(&timezone).naive_local().date();
+        let per_day = self.per_day();
+
+        // Everything outside of the runs of DST-free days gets scanned, which is the partial
+        // days at either end and the days with a transition. Scanning from one run to the next
+        // only visits the matches in between.
+        let mut count = 0;
+        let mut scan_after = after.clone();
+        let runs = self.uniform_offset_runs(&timezone, first_date, Some(last_date));
+        for run in runs {
+            count += self.scan_count(&scan_after, Excluded(&run.start));
+            count += run.days * per_day;
+            scan_after = run.end;
+        }
+        count + self.scan_count(&scan_after, Excluded(&before))
     }
 
-    /// The same, but with a static ownership.
-    pub fn after_owned<Z: TimeZone>(&self, after: DateTime<Z>) -> OwnedScheduleIterator<Z> {
-        OwnedScheduleIterator::new(self.clone(), after)
+    pub(crate) fn nth_after<D>(&self, after: &D, n: u64) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let timezone = after.time_zone();
+        let per_day = self.per_day();
+
+        let mut remaining = n;
+        let mut scan_after = after.clone();
+        let runs = self.uniform_offset_runs(&timezone, after.naive_local().date(), None);
+        for run in runs {
+            match self.scan_nth(&scan_after, Excluded(&run.start), remaining) {
+                Ok(found) => return Some(found),
+                Err(skipped) => remaining -= skipped,
+            }
+            if remaining < run.days * per_day {
+                let date = self.nth_matching_date(run.first, remaining / per_day)?;
+                let day_start = timezone.at_local(&date.and_hms_opt(0, 0, 0)?).single()?;
+                return self.nth_in_day(&day_start, remaining % per_day);
+            }
+            remaining -= run.days * per_day;
+            scan_after = run.end;
+        }
+        self.scan_nth(&scan_after, Unbounded, remaining).ok()
+    }
+
+    pub(crate) fn dst_anomalies<Z, R>(&self, timezone: &Z, dates: R) -> Vec<DstAnomaly<Z::DateTime>>
+    where
+        Z: ScheduleTimeZone,
+        R: RangeBounds<NaiveDate>,
+    {
+        let mut anomalies = Vec::new();
+        let dates = self.matching_dates(dates.start_bound().cloned(), dates.end_bound().cloned());
+        for date in dates {
+            // Days with the same offset from start to end have no transition to look at.
+            if uniform_offset_day(timezone, date).is_some() {
+                continue;
+            }
+            let (Some(day_start), Some(next_day)) = (
+                date.and_hms_opt(0, 0, 0),
+                date.succ_opt().and_then(|next| next.and_hms_opt(0, 0, 0)),
+            ) else {
+                continue;
+            };
+            let mut cursor = day_start - Duration::nanoseconds(1);
+            while let Some(local) = self.next_within(&cursor, Excluded(&next_day)) {
+                cursor = local;
+                let anomaly = match timezone.at_local(&local) {
+                    LocalResult::None => DstAnomaly::skipped(timezone, local),
+                    LocalResult::Ambiguous(earlier, later) => Some(DstAnomaly::Repeated {
+                        local,
+                        earlier,
+                        later,
+                    }),
+                    LocalResult::Single(_) => None,
+                };
+                anomalies.extend(anomaly);
+            }
+        }
+        anomalies
+    }
+
+    pub(crate) fn includes<D>(&self, date_time: D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        let local = date_time.naive_local();
+        if self.fields.dst_gap_policy == DstGapPolicy::ShiftForward {
+            if let Some(before_gap) = dst_gap_before(&date_time) {
+                if self.next_within(&before_gap, Excluded(&local)).is_some() {
+                    return true;
+                }
+            }
+        }
+        if !self.includes_naive(local) {
+            return false;
+        }
+        match date_time.time_zone().at_local(&local) {
+            LocalResult::Ambiguous(earlier, later) => match self.fields.dst_fold_policy {
+                DstFoldPolicy::Both => true,
+                DstFoldPolicy::EarlierOnly => date_time == earlier,
+                DstFoldPolicy::LaterOnly => date_time == later,
+            },
+            _ => true,
+        }
+    }
+
+    pub(crate) fn includes_naive(&self, local: NaiveDateTime) -> bool {
+        self.includes_day(local.year() as Ordinal, local.month(), local.day())
+            && self.includes_hour(local.hour() as Ordinal)
+            && self.includes_minute(local.minute() as Ordinal)
+            && self.includes_second(local.second() as Ordinal)
     }
 
-    pub fn includes<Z>(&self, date_time: DateTime<Z>) -> bool
+    /// Counts the matches between two instants one at a time.
+    fn scan_count<D>(&self, after: &D, until: Bound<&D>) -> u64
     where
-        Z: TimeZone,
+        D: ScheduleDateTime,
     {
-        self.fields.years.includes(date_time.year() as Ordinal)
-            && self.fields.months.includes(date_time.month() as Ordinal)
-            && self
-                .fields
-                .days_of_week
-                .includes(date_time.weekday().number_from_sunday())
-            && self
-                .fields
-                .days_of_month
-                .includes(date_time.day() as Ordinal)
-            && self.fields.hours.includes(date_time.hour() as Ordinal)
-            && self.fields.minutes.includes(date_time.minute() as Ordinal)
-            && self.fields.seconds.includes(date_time.second() as Ordinal)
+        let mut cursor = after.clone();
+        let mut count = 0;
+        while let Some(next) = self.next_within(&cursor, until) {
+            count += 1;
+            cursor = next;
+        }
+        count
     }
 
-    /// Returns a [TimeUnitSpec] describing the years included in this [Schedule].
-    pub fn years(&self) -> &impl TimeUnitSpec {
-        &self.fields.years
+    /// Finds the `n`th match between two instants one at a time, or returns how many matches
+    /// there were if there aren't enough.
+    fn scan_nth<D>(&self, after: &D, until: Bound<&D>, n: u64) -> Result<D, u64>
+    where
+        D: ScheduleDateTime,
+    {
+        let mut cursor = after.clone();
+        let mut count = 0;
+        while let Some(next) = self.next_within(&cursor, until) {
+            if count == n {
+                return Ok(next);
+            }
+            count += 1;
+            cursor = next;
+        }
+        Err(count)
     }
 
-    /// Returns a [TimeUnitSpec] describing the months of the year included in this [Schedule].
-    pub fn months(&self) -> &impl TimeUnitSpec {
-        &self.fields.months
+    /// Returns the `n`th match on a matching day without a DST transition, which is the `n`th
+    /// combination of hours, minutes and seconds.
+    fn nth_in_day<D>(&self, day_start: &D, n: u64) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        let seconds = self.fields.seconds.count() as u64;
+        let per_hour = self.fields.minutes.count() as u64 * seconds;
+        let hour = self.fields.hours.iter().nth((n / per_hour) as usize)?;
+        let minute = self
+            .fields
+            .minutes
+            .iter()
+            .nth((n % per_hour / seconds) as usize)?;
+        let second = self.fields.seconds.iter().nth((n % seconds) as usize)?;
+        day_start
+            .time_zone()
+            .at_local(
+                &day_start
+                    .naive_local()
+                    .date()
+                    .and_hms_opt(hour, minute, second)?,
+            )
+            .single()
     }
 
-    /// Returns a [TimeUnitSpec] describing the days of the month included in this [Schedule].
-    pub fn days_of_month(&self) -> &impl TimeUnitSpec {
-        &self.fields.days_of_month
+    /// Returns the runs of matching days strictly between `after` and `before` that have one UTC
+    /// offset throughout, as whole years where possible, then as whole months and single days.
+    fn uniform_offset_runs<'b, Z>(
+        self,
+        timezone: &'b Z,
+        after: NaiveDate,
+        before: Option<NaiveDate>,
+    ) -> impl Iterator<Item = UniformRun<Z::DateTime>> + 'b
+    where
+        'a: 'b,
+        Z: ScheduleTimeZone,
+    {
+        let start = Excluded(after);
+        let end = before.map_or(Unbounded, Excluded);
+        let years = (
+            Included(year_ordinal(after.year())),
+            before.map_or(Unbounded, |before| Included(year_ordinal(before.year()))),
+        );
+        self.fields
+            .years
+            .ordinals()
+            .range(years)
+            .flat_map(move |year| {
+                let masks: [u32; 12] = core::array::from_fn(|month0| {
+                    self.day_mask_within(year, month0 as Ordinal + 1, start, end)
+                });
+                let year_run = uniform_run(timezone, year, 1, &masks);
+                let months = year_run.is_none().then(move || {
+                    (1..=12).flat_map(move |month| {
+                        let mask = masks[month as usize - 1];
+                        let month_run = uniform_run(timezone, year, month, &[mask]);
+                        let days = month_run.is_none().then(move || {
+                            MaskDays::new(mask, 1, 31).filter_map(move |day| {
+                                uniform_run(timezone, year, month, &[1 << day])
+                            })
+                        });
+                        month_run.into_iter().chain(days.into_iter().flatten())
+                    })
+                });
+                year_run.into_iter().chain(months.into_iter().flatten())
+            })
     }
 
-    /// Returns a [TimeUnitSpec] describing the days of the week included in this [Schedule].
-    pub fn days_of_week(&self) -> &impl TimeUnitSpec {
-        &self.fields.days_of_week
+    /// Returns the `n`th date, counting from zero, that matches the date fields from `first`
+    /// onwards. Months are skipped by the number of days they match.
+    fn nth_matching_date(&self, first: NaiveDate, n: u64) -> Option<NaiveDate> {
+        let mut remaining = n;
+        let years = self
+            .fields
+            .years
+            .ordinals()
+            .range((Included(year_ordinal(first.year())), Unbounded));
+        for year in years {
+            for month in self.fields.months.iter() {
+                let mask = self.day_mask_within(year, month, Included(first), Unbounded);
+                let days = mask.count_ones() as u64;
+                if remaining < days {
+                    let day = MaskDays::new(mask, 1, 31).nth(remaining as usize)?;
+                    return NaiveDate::from_ymd_opt(year as i32, month, day);
+                }
+                remaining -= days;
+            }
+        }
+        None
     }
 
-    /// Returns a [TimeUnitSpec] describing the hours of the day included in this [Schedule].
-    pub fn hours(&self) -> &impl TimeUnitSpec {
-        &self.fields.hours
+    /// Returns the dates between `start` and `end` that match the date fields, in ascending
+    /// order.
+    fn matching_dates(
+        &self,
+        start: Bound<NaiveDate>,
+        end: Bound<NaiveDate>,
+    ) -> impl Iterator<Item = NaiveDate> + '_ {
+        let year = |bound: Bound<&NaiveDate>| match bound {
+            Included(date) | Excluded(date) => Included(year_ordinal(date.year())),
+            Unbounded => Unbounded,
+        };
+        let years = (year(start.as_ref()), year(end.as_ref()));
+        self.fields
+            .years
+            .ordinals()
+            .range(years)
+            .flat_map(move |year| {
+                self.fields.months.ordinals().iter().flat_map(move |month| {
+                    let mask = self.day_mask_within(year, month, start, end);
+                    MaskDays::new(mask, 1, 31)
+                        .filter_map(move |day| NaiveDate::from_ymd_opt(year as i32, month, day))
+                })
+            })
     }
 
-    /// Returns a [TimeUnitSpec] describing the minutes of the hour included in this [Schedule].
-    pub fn minutes(&self) -> &impl TimeUnitSpec {
-        &self.fields.minutes
+    /// Returns the days of a month matched by all of the date fields between `start` and `end`.
+    fn day_mask_within(
+        &self,
+        year: Ordinal,
+        month: Ordinal,
+        start: Bound<NaiveDate>,
+        end: Bound<NaiveDate>,
+    ) -> u32 {
+        // Days are compared as `(year, month, day)`, so bounds in other months either keep or
+        // drop the whole month.
+        let key = |date: &NaiveDate| (date.year(), date.month(), date.day());
+        let this_month = (year as i32, month);
+        let first = match start {
+            Included(date) => Some((key(&date), 0)),
+            Excluded(date) => Some((key(&date), 1)),
+            Unbounded => None,
+        };
+        let last = match end {
+            Included(date) => Some((key(&date), 0)),
+            Excluded(date) => Some((key(&date), 1)),
+            Unbounded => None,
+        };
+        // Bits from `day` upwards, and bits up to and including `day`.
+        let from = |day: u32| !(((1u64 << day) - 1) as u32);
+        let through = |day: u32| ((2u64 << day) - 1) as u32;
+        let mut mask = u32::MAX;
+        if let Some(((first_year, first_month, first_day), skip)) = first {
+            match this_month.cmp(&(first_year, first_month)) {
+                Ordering::Less => return 0,
+                Ordering::Equal => mask &= from(first_day + skip),
+                Ordering::Greater => {}
+            }
+        }
+        if let Some(((last_year, last_month, last_day), skip)) = last {
+            match this_month.cmp(&(last_year, last_month)) {
+                Ordering::Greater => return 0,
+                Ordering::Equal => mask &= through(last_day - skip),
+                Ordering::Less => {}
+            }
+        }
+        if mask == 0 {
+            return 0;
+        }
+        mask & self.day_mask(year, month)
     }
 
-    /// Returns a [TimeUnitSpec] describing the seconds of the minute included in this [Schedule].
-    pub fn seconds(&self) -> &impl TimeUnitSpec {
-        &self.fields.seconds
+    fn includes_hour(&self, hour: Ordinal) -> bool {
+        match self.tables {
+            Some(tables) => tables.hours().includes(hour),
+            None => self.fields.hours.includes(hour),
+        }
     }
 
-    pub fn timeunitspec_eq(&self, other: &Schedule) -> bool {
-        self.fields == other.fields
+    fn includes_minute(&self, minute: Ordinal) -> bool {
+        match self.tables {
+            Some(tables) => tables.minutes().includes(minute),
+            None => self.fields.minutes.includes(minute),
+        }
     }
 
-    /// Returns a reference to the source cron expression.
-    pub fn source(&self) -> &str {
-        &self.source
+    fn includes_second(&self, second: Ordinal) -> bool {
+        match self.tables {
+            Some(tables) => tables.seconds().includes(second),
+            None => self.fields.seconds.includes(second),
+        }
     }
-}
 
-impl Display for Schedule {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{}", self.source)
+    /// Returns the number of times of day the schedule matches.
+    fn per_day(&self) -> u64 {
+        match self.tables {
+            Some(tables) => tables.per_day(),
+            None => {
+                self.fields.hours.count() as u64
+                    * self.fields.minutes.count() as u64
+                    * self.fields.seconds.count() as u64
+            }
+        }
     }
-}
 
-impl PartialEq for Schedule {
-    fn eq(&self, other: &Schedule) -> bool {
-        self.source == other.source
+    /// Returns true if a day is matched by all of the date fields.
+    fn includes_day(&self, year: Ordinal, month: Ordinal, day: Ordinal) -> bool {
+        match self.tables {
+            Some(tables) => tables.day_mask(year, month) & (1 << day) != 0,
+            None => {
+                self.fields.years.includes(year)
+                    && self.fields.months.includes(month)
+                    && self.fields.includes_day(year, month, day)
+            }
+        }
     }
-}
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ScheduleFields {
-    years: Years,
-    days_of_week: DaysOfWeek,
-    months: Months,
-    days_of_month: DaysOfMonth,
-    hours: Hours,
-    minutes: Minutes,
-    seconds: Seconds,
-}
 
-impl ScheduleFields {
-    pub(crate) fn new(
-        seconds: Seconds,
-        minutes: Minutes,
-        hours: Hours,
-        days_of_month: DaysOfMonth,
-        months: Months,
-        days_of_week: DaysOfWeek,
-        years: Years,
-    ) -> ScheduleFields {
-        ScheduleFields {
-            years,
-            days_of_week,
-            months,
-            days_of_month,
-            hours,
-            minutes,
-            seconds,
+    /// Returns the days of a month matched by all of the date fields, one bit per day.
+    fn day_mask(&self, year: Ordinal, month: Ordinal) -> u32 {
+        match self.tables {
+            Some(tables) => tables.day_mask(year, month),
+            None => self.fields.day_mask(year, month),
         }
     }
+
+    /// Returns the days between `first` and `last` inclusive that are matched by all of the date
+    /// fields other than years and months, in ascending order.
+    fn days_of_month_in(
+        &self,
+        year: Ordinal,
+        month: Ordinal,
+        first: Ordinal,
+        last: Ordinal,
+    ) -> impl DoubleEndedIterator<Item = Ordinal> + 'a {
+        let fields = self.fields;
+        let compiled = self.tables.map(|tables| {
+            let last = last.min(days_in_month(month, year));
+            MaskDays::new(tables.day_mask(year, month), first.min(last), last)
+        });
+        let scanned = compiled
+            .is_none()
+            .then(|| fields.days_of_month_in(year, month, first, last));
+        compiled
+            .into_iter()
+            .flatten()
+            .chain(scanned.into_iter().flatten())
+    }
 }
 
 pub struct ScheduleIterator<'a, Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
-    schedule: &'a Schedule,
-    previous_datetime: Option<DateTime<Z>>,
+    matcher: Matcher<'a>,
+    previous_datetime: Option<Z::DateTime>,
 }
-//TODO: Cutoff datetime?
 
 impl<'a, Z> ScheduleIterator<'a, Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
-    fn new(schedule: &'a Schedule, starting_datetime: &DateTime<Z>) -> Self {
+    /// Iterates from `starting_datetime`, or over nothing if there's no start.
+    pub(crate) fn new(matcher: Matcher<'a>, starting_datetime: Option<Z::DateTime>) -> Self {
         ScheduleIterator {
-            schedule,
-            previous_datetime: Some(starting_datetime.clone()),
+            matcher,
+            previous_datetime: starting_datetime,
         }
     }
 }
 
 impl<Z> Iterator for ScheduleIterator<'_, Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
-    type Item = DateTime<Z>;
+    type Item = Z::DateTime;
 
-    fn next(&mut self) -> Option<DateTime<Z>> {
+    fn next(&mut self) -> Option<Z::DateTime> {
         let previous = self.previous_datetime.take()?;
 
-        let next = self.schedule.next_after(&previous)?;
+        let next = self.matcher.next_within(&previous, Unbounded)?;
         self.previous_datetime = Some(next.clone());
         Some(next)
     }
@@ -581,63 +1873,347 @@ where
 
 impl<Z> DoubleEndedIterator for ScheduleIterator<'_, Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         let previous = self.previous_datetime.take()?;
 
-        let prev = self.schedule.prev_from(&previous)?;
+        let prev = self.matcher.prev_within(&previous, Unbounded)?;
         self.previous_datetime = Some(prev.clone());
         Some(prev)
     }
 }
 
+/// A `ScheduleIterator` over wall clock times with no time zone.
+pub type NaiveScheduleIterator<'a> = ScheduleIterator<'a, NaiveZone>;
+
 /// A `ScheduleIterator` with a static lifetime.
 pub struct OwnedScheduleIterator<Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
     schedule: Schedule,
-    previous_datetime: Option<DateTime<Z>>,
+    tables: Option<Arc<Tables>>,
+    previous_datetime: Option<Z::DateTime>,
 }
 
 impl<Z> OwnedScheduleIterator<Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
-    pub fn new(schedule: Schedule, starting_datetime: DateTime<Z>) -> Self {
+    pub fn new<D>(schedule: Schedule, starting_datetime: D) -> Self
+    where
+        D: ScheduleDateTime<TimeZone = Z>,
+        Z: ScheduleTimeZone<DateTime = D>,
+    {
+        Self::starting_at(schedule, None, Some(starting_datetime))
+    }
+
+    /// Iterates over a schedule from `starting_datetime`, using the tables compiled for it if
+    /// there are any, or over nothing if there's no start.
+    pub(crate) fn starting_at(
+        schedule: Schedule,
+        tables: Option<Arc<Tables>>,
+        starting_datetime: Option<Z::DateTime>,
+    ) -> Self {
         Self {
             schedule,
-            previous_datetime: Some(starting_datetime),
+            tables,
+            previous_datetime: starting_datetime,
         }
     }
+
+    fn matcher(&self) -> Matcher<'_> {
+        Matcher::new(&self.schedule, self.tables.as_deref())
+    }
 }
 
 impl<Z> Iterator for OwnedScheduleIterator<Z>
 where
-    Z: TimeZone,
+    Z: ScheduleTimeZone,
 {
-    type Item = DateTime<Z>;
+    type Item = Z::DateTime;
 
-    fn next(&mut self) -> Option<DateTime<Z>> {
+    fn next(&mut self) -> Option<Z::DateTime> {
         let previous = self.previous_datetime.take()?;
 
-        let next = self.schedule.next_after(&previous)?;
+        let next = self.matcher().next_within(&previous, Unbounded)?;
         self.previous_datetime = Some(next.clone());
         Some(next)
     }
 }
 
-impl<Z: TimeZone> DoubleEndedIterator for OwnedScheduleIterator<Z> {
+impl<Z: ScheduleTimeZone> DoubleEndedIterator for OwnedScheduleIterator<Z> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let previous = self.previous_datetime.take()?;
 
-        let prev = self.schedule.prev_from(&previous)?;
+        let prev = self.matcher().prev_within(&previous, Unbounded)?;
         self.previous_datetime = Some(prev.clone());
         Some(prev)
     }
 }
 
+/// An iterator over the DateTimes that match a schedule within a range, from either end.
+///
+/// Each end of the range moves inwards as values are taken from it, so the iterator is exhausted
+/// once both ends meet.
+pub struct ScheduleRangeIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    matcher: Matcher<'a>,
+    start: Bound<Z::DateTime>,
+    end: Bound<Z::DateTime>,
+}
+
+impl<'a, Z> ScheduleRangeIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    pub(crate) fn new(
+        matcher: Matcher<'a>,
+        start: Bound<Z::DateTime>,
+        end: Bound<Z::DateTime>,
+    ) -> Self {
+        ScheduleRangeIterator {
+            matcher,
+            start,
+            end,
+        }
+    }
+
+    /// The instant just before the first DateTime in range, falling back to the start of the
+    /// first supported year in the end's timezone.
+    fn before_start(&self) -> Option<Z::DateTime> {
+        match &self.start {
+            Included(start) => start.checked_add_signed(-Duration::nanoseconds(1)),
+            Excluded(start) => Some(start.clone()),
+            Unbounded => {
+                let timezone = match &self.end {
+                    Included(end) | Excluded(end) => end.time_zone(),
+                    Unbounded => return None,
+                };
+                let first_year = Years::inclusive_min();
+                let first = timezone
+                    .at_local(&local_ymd_hms(first_year, 1, 1, 0, 0, 0)?)
+                    .earliest()?;
+                first.checked_add_signed(-Duration::nanoseconds(1))
+            }
+        }
+    }
+
+    /// The instant just after the last DateTime in range, falling back to the end of the last
+    /// supported year in the start's timezone.
+    fn after_end(&self) -> Option<Z::DateTime> {
+        match &self.end {
+            Included(end) => end.checked_add_signed(Duration::nanoseconds(1)),
+            Excluded(end) => Some(end.clone()),
+            Unbounded => {
+                let timezone = match &self.start {
+                    Included(start) | Excluded(start) => start.time_zone(),
+                    Unbounded => return None,
+                };
+                let after_last_year = Years::inclusive_max() + 1;
+                timezone
+                    .at_local(&local_ymd_hms(after_last_year, 1, 1, 0, 0, 0)?)
+                    .latest()
+            }
+        }
+    }
+}
+
+impl<Z> Iterator for ScheduleRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    type Item = Z::DateTime;
+
+    fn next(&mut self) -> Option<Z::DateTime> {
+        let after = self.before_start()?;
+        let next = self.matcher.next_within(&after, self.end.as_ref())?;
+        self.start = Excluded(next.clone());
+        Some(next)
+    }
+}
+
+impl<Z> DoubleEndedIterator for ScheduleRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let before = self.after_end()?;
+        let prev = self.matcher.prev_within(&before, self.start.as_ref())?;
+        self.end = Excluded(prev.clone());
+        Some(prev)
+    }
+}
+
+/// A run of matching days with one UTC offset throughout, whose matches can be counted from the
+/// number of hours, minutes and seconds in the schedule.
+struct UniformRun<D> {
+    /// The first matching day.
+    first: NaiveDate,
+    /// The number of matching days.
+    days: u64,
+    /// The first instant of the first matching day.
+    start: D,
+    /// The last instant of the last matching day.
+    end: D,
+}
+
+/// Returns the run of the days set in `masks`, which hold consecutive months of `year` from
+/// `first_month`, if the UTC offset is the same from the start of the first day to the end of the
+/// last.
+///
+/// The offset is checked at the start of every day of the run and at its end, so every DST
+/// transition between two days of the run is noticed, however short the DST period. Like
+/// [uniform_offset_day], a transition that is undone within the same day isn't.
+fn uniform_run<Z>(
+    timezone: &Z,
+    year: Ordinal,
+    first_month: Ordinal,
+    masks: &[u32],
+) -> Option<UniformRun<Z::DateTime>>
+where
+    Z: ScheduleTimeZone,
+{
+    let days = masks.iter().map(|mask| mask.count_ones() as u64).sum();
+    let (first_index, first_mask) = masks.iter().enumerate().find(|(_, &mask)| mask != 0)?;
+    let (last_index, last_mask) = masks.iter().enumerate().rfind(|(_, &mask)| mask != 0)?;
+    let date = |index: usize, day: u32| {
+        NaiveDate::from_ymd_opt(year as i32, first_month + index as Ordinal, day)
+    };
+    let first = date(first_index, first_mask.trailing_zeros())?;
+    let last = date(last_index, 31 - last_mask.leading_zeros())?;
+
+    let start = timezone.at_local(&first.and_hms_opt(0, 0, 0)?).single()?;
+    let end = timezone
+        .at_local(&last.and_hms_nano_opt(23, 59, 59, 999_999_999)?)
+        .single()?;
+    let offset = start.utc_offset_seconds();
+    if end.utc_offset_seconds() != offset {
+        return None;
+    }
+    for sample in first
+        .iter_days()
+        .skip(1)
+        .take_while(|sample| *sample <= last)
+    {
+        let at = timezone.at_local(&sample.and_hms_opt(0, 0, 0)?).single()?;
+        if at.utc_offset_seconds() != offset {
+            return None;
+        }
+    }
+    Some(UniformRun {
+        first,
+        days,
+        start,
+        end,
+    })
+}
+
+/// Returns the first and last instants of `date` if the whole day has the same UTC offset, or
+/// `None` if a DST transition happens during the day.
+fn uniform_offset_day<Z>(timezone: &Z, date: NaiveDate) -> Option<(Z::DateTime, Z::DateTime)>
+where
+    Z: ScheduleTimeZone,
+{
+    let start = timezone.at_local(&date.and_hms_opt(0, 0, 0)?).single()?;
+    let end = timezone
+        .at_local(&date.and_hms_nano_opt(23, 59, 59, 999_999_999)?)
+        .single()?;
+    if start.utc_offset_seconds() != end.utc_offset_seconds() {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Builds a wall clock time, or returns `None` for a date that doesn't exist, like the 31st of a
+/// 30 day month.
+fn local_ymd_hms(
+    year: Ordinal,
+    month: Ordinal,
+    day: Ordinal,
+    hour: Ordinal,
+    minute: Ordinal,
+    second: Ordinal,
+) -> Option<NaiveDateTime> {
+    NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(hour, minute, second)
+}
+
+/// Returns the first instant after the DST gap containing the wall clock time `local`.
+///
+/// Gaps end on a whole second, so this finds the first second after `local` that exists in
+/// `timezone`. No gap is longer than a day.
+pub(crate) fn dst_gap_end<Z>(timezone: &Z, local: &NaiveDateTime) -> Option<Z::DateTime>
+where
+    Z: ScheduleTimeZone,
+{
+    let exists = |seconds: i64| {
+        timezone
+            .at_local(&(*local + Duration::seconds(seconds)))
+            .earliest()
+    };
+    let (mut missing, mut present) = (0, 26 * 60 * 60);
+    exists(present)?;
+    while present - missing > 1 {
+        let middle = missing + (present - missing) / 2;
+        match exists(middle) {
+            Some(_) => present = middle,
+            None => missing = middle,
+        }
+    }
+    exists(present)
+}
+
+/// Returns the wall clock time just before `date_time` if a DST gap ends at `date_time`.
+pub(crate) fn dst_gap_before<D>(date_time: &D) -> Option<NaiveDateTime>
+where
+    D: ScheduleDateTime,
+{
+    let local = date_time.naive_local();
+    let before = date_time
+        .checked_add_signed(-Duration::nanoseconds(1))?
+        .naive_local();
+    (before + Duration::nanoseconds(1) < local).then_some(before)
+}
+
+/// Discards a forward search result that lies past the end of a range.
+fn bounded_above<D>(candidate: D, until: Bound<&D>) -> Option<D>
+where
+    D: ScheduleDateTime,
+{
+    match until {
+        Included(end) if candidate > *end => None,
+        Excluded(end) if candidate >= *end => None,
+        _ => Some(candidate),
+    }
+}
+
+/// Discards a reverse search result that lies before the start of a range.
+fn bounded_below<D>(candidate: D, since: Bound<&D>) -> Option<D>
+where
+    D: ScheduleDateTime,
+{
+    match since {
+        Included(start) if candidate < *start => None,
+        Excluded(start) if candidate <= *start => None,
+        _ => Some(candidate),
+    }
+}
+
+/// Returns the first ordinal after `ordinal` that `field` doesn't include.
+fn first_gap_after<T: TimeUnitField>(field: &T, ordinal: Ordinal) -> Option<Ordinal> {
+    (ordinal + 1..=T::inclusive_max()).find(|&next| !field.includes(next))
+}
+
+/// Returns the last ordinal before `ordinal` that `field` doesn't include.
+fn last_gap_before<T: TimeUnitField>(field: &T, ordinal: Ordinal) -> Option<Ordinal> {
+    (T::inclusive_min()..ordinal)
+        .rev()
+        .find(|&previous| !field.includes(previous))
+}
+
 fn is_leap_year(year: Ordinal) -> bool {
     let by_four = year.is_multiple_of(4);
     let by_hundred = year.is_multiple_of(100);
@@ -645,7 +2221,7 @@ fn is_leap_year(year: Ordinal) -> bool {
     by_four && ((!by_hundred) || by_four_hundred)
 }
 
-fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
+pub(crate) fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
     let is_leap_year = is_leap_year(year);
     match month {
         9 | 4 | 6 | 11 => 30,
@@ -720,9 +2296,9 @@ impl<'de> Deserialize<'de> for Schedule {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
-    use chrono::Duration;
+    use chrono::{Duration, TimeZone, Utc};
     #[cfg(feature = "serde")]
     use serde_test::{assert_tokens, Token};
 
@@ -794,6 +2370,18 @@ mod test {
         .eq(schedule.after(&starting_date).take(7)));
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_de_schedule_options() {
+        let schedule = Schedule::from_str("0 30 2 * * ?")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::ShiftForward);
+        assert_tokens(
+            &schedule,
+            &[Token::String("0 30 2 * * ?; dst-gap=shift-forward")],
+        );
+    }
+
     #[test]
     fn test_next_and_prev_from() {
         let expression = "0 5,13,40-42 17 1 Jan *";
@@ -807,12 +2395,12 @@ mod test {
         println!("NEXT2 AFTER for {} {:?}", expression, next2);
         assert!(next2.is_some());
 
-        let prev = schedule.prev_from(&next2.unwrap());
+        let prev = schedule.prev_before(&next2.unwrap());
         println!("PREV FROM for {} {:?}", expression, prev);
         assert!(prev.is_some());
         assert_eq!(prev, next);
 
-        let prev2 = schedule.prev_from(&(next2.unwrap() + Duration::nanoseconds(100)));
+        let prev2 = schedule.prev_before(&(next2.unwrap() + Duration::nanoseconds(100)));
         println!("PREV2 FROM for {} {:?}", expression, prev2);
         assert!(prev2.is_some());
         assert_eq!(prev2, next2);
@@ -836,7 +2424,7 @@ mod test {
     fn test_prev_from() {
         let expression = "0 5,13,40-42 17 1 Jan *";
         let schedule = Schedule::from_str(expression).unwrap();
-        let prev = schedule.prev_from(&Utc::now());
+        let prev = schedule.prev_before(&Utc::now());
         println!("PREV FROM for {} {:?}", expression, prev);
         assert!(prev.is_some());
     }
@@ -850,6 +2438,62 @@ mod test {
         assert!(next.is_some());
     }
 
+    #[test]
+    fn test_next_within_and_prev_within_stop_at_bounds() {
+        let schedule = Schedule::from_str("0 0 0 29 2 ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap();
+        assert_eq!(None, schedule.matcher().next_within(&start, Excluded(&end)));
+        assert_eq!(
+            Some(end),
+            schedule.matcher().next_within(&start, Included(&end))
+        );
+        let after_end = end + Duration::days(1);
+        assert_eq!(
+            None,
+            schedule.matcher().prev_within(&after_end, Excluded(&end))
+        );
+        assert_eq!(
+            Some(end),
+            schedule.matcher().prev_within(&after_end, Included(&end))
+        );
+        assert_eq!(None, schedule.matcher().next_within(&end, Included(&start)));
+    }
+
+    #[test]
+    fn test_inclusive_and_exclusive_queries() {
+        let schedule = Schedule::from_str("0 0/30 * * * ?").unwrap();
+        let on_match = Utc.with_ymd_and_hms(2024, 1, 1, 9, 30, 0).unwrap();
+        let between = on_match + Duration::milliseconds(1);
+        let half_hour = Duration::minutes(30);
+
+        assert_eq!(Some(on_match + half_hour), schedule.next_after(&on_match));
+        assert_eq!(Some(on_match), schedule.next_at_or_after(&on_match));
+        assert_eq!(
+            Some(on_match + half_hour),
+            schedule.next_at_or_after(&between)
+        );
+
+        assert_eq!(Some(on_match - half_hour), schedule.prev_before(&on_match));
+        assert_eq!(Some(on_match), schedule.prev_at_or_before(&on_match));
+        assert_eq!(Some(on_match), schedule.prev_before(&between));
+        assert_eq!(Some(on_match), schedule.prev_at_or_before(&between));
+    }
+
+    #[test]
+    fn test_before_matches_rev_after() {
+        let schedule = Schedule::from_str("0 0,30 0,6,12,18 1,15 Jan-March Thurs").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert!(schedule
+            .before(&start)
+            .take(5)
+            .eq(schedule.after(&start).rev().take(5)));
+        assert!(schedule
+            .before_owned(start)
+            .take(5)
+            .eq(schedule.before(&start).take(5)));
+    }
+
     #[test]
     fn test_upcoming_utc() {
         let expression = "0 0,30 0,6,12,18 1,15 Jan-March Thurs";
@@ -961,6 +2605,145 @@ mod test {
         assert!(schedule.is_err());
     }
 
+    #[test]
+    fn test_options_round_trip_through_source() {
+        let schedule = Schedule::from_str("0 30 1 * * ?")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::ShiftForward)
+            .with_dst_fold_policy(DstFoldPolicy::LaterOnly);
+        assert_eq!(
+            "0 30 1 * * ?; dst-gap=shift-forward; dst-fold=later-only",
+            schedule.source()
+        );
+        assert_eq!(schedule, Schedule::from_str(schedule.source()).unwrap());
+        assert_eq!(schedule, Schedule::from_str(&schedule.to_string()).unwrap());
+
+        let schedule = schedule
+            .with_dst_gap_policy(DstGapPolicy::Skip)
+            .with_dst_fold_policy(DstFoldPolicy::EarlierOnly);
+        assert_eq!("0 30 1 * * ?; dst-fold=earlier-only", schedule.source());
+        assert_eq!(
+            Schedule::from_str("0 30 1 * * ?").unwrap(),
+            schedule.with_dst_fold_policy(DstFoldPolicy::Both)
+        );
+    }
+
+    #[test]
+    fn test_anchored_step_round_trips_through_source() {
+        let anchor = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let schedule = Schedule::from_str("0 0 9 ? * Tue")
+            .unwrap()
+            .with_anchored_step(AnchoredStep::weeks(anchor, 2));
+        assert_eq!("0 0 9 ? * Tue; step=2w@2024-01-02", schedule.source());
+        assert_eq!(schedule, Schedule::from_str(schedule.source()).unwrap());
+
+        let schedule = schedule.with_anchored_step(AnchoredStep::months(anchor, 3));
+        assert_eq!("0 0 9 ? * Tue; step=3m@2024-01-02", schedule.source());
+        let parsed = Schedule::from_str("@daily; step = 45d@2024-01-02").unwrap();
+        assert_eq!(
+            Some(&AnchoredStep::days(anchor, 45)),
+            parsed.anchored_step()
+        );
+
+        assert!(Schedule::from_str("@daily; step=0d@2024-01-02").is_err());
+        assert!(Schedule::from_str("@daily; step=2y@2024-01-02").is_err());
+        assert!(Schedule::from_str("@daily; step=2w").is_err());
+        assert!(Schedule::from_str("@daily; step=@2024-01-02").is_err());
+        assert!(Schedule::from_str("@daily; step=2w@2024-02-30").is_err());
+        assert!(Schedule::from_str("@daily; step=2é@2024-01-02").is_err());
+    }
+
+    #[test]
+    fn test_weeks_of_year_round_trip_through_source() {
+        let schedule = Schedule::from_str("0 0 9 ? * Mon")
+            .unwrap()
+            .with_weeks_of_year(" 1-10,odd ")
+            .unwrap();
+        assert_eq!("0 0 9 ? * Mon; weeks-of-year=1-10,odd", schedule.source());
+        assert_eq!(schedule, Schedule::from_str(schedule.source()).unwrap());
+        assert_eq!(
+            Schedule::from_str("0 0 9 ? * Mon").unwrap(),
+            schedule.with_weeks_of_year("*").unwrap()
+        );
+        assert!(Schedule::from_str("0 0 9 ? * Mon; weeks-of-year=54").is_err());
+    }
+
+    #[test]
+    fn test_days_of_year_round_trip_through_source() {
+        let schedule = Schedule::from_str("0 0 2 * * ?")
+            .unwrap()
+            .with_days_of_year("1,91,182,274")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::ShiftForward)
+            .with_days_of_year("1/100")
+            .unwrap();
+        assert_eq!(
+            "0 0 2 * * ?; days-of-year=1/100; dst-gap=shift-forward",
+            schedule.source()
+        );
+        assert_eq!(schedule, Schedule::from_str(schedule.source()).unwrap());
+        assert!(Schedule::from_str("0 0 2 * * ?; days-of-year=367").is_err());
+    }
+
+    #[test]
+    fn test_quarter_day_round_trips_through_source() {
+        let written = Schedule::from_str("0 0 18 lwq Q4 ?").unwrap();
+        assert_eq!(Some(QuarterDay::LastWeekday), written.quarter_day());
+        assert!(written.days_of_month().is_all());
+        assert_eq!(
+            "0 0 18 lwq Q4 ?",
+            written
+                .clone()
+                .with_quarter_day(QuarterDay::LastWeekday)
+                .source()
+        );
+
+        let changed = written.with_quarter_day(QuarterDay::First);
+        assert_eq!("0 0 18 lwq Q4 ?; quarter-day=1Q", changed.source());
+        assert_eq!(changed, Schedule::from_str(changed.source()).unwrap());
+
+        let monthly = Schedule::from_str("@monthly")
+            .unwrap()
+            .with_quarter_day(QuarterDay::FirstWeekday);
+        assert_eq!("@monthly; quarter-day=1WQ", monthly.source());
+        assert_eq!(monthly, Schedule::from_str(monthly.source()).unwrap());
+
+        assert!(Schedule::from_str("0 0 18 2Q Q4 ?").is_err());
+        assert!(Schedule::from_str("0 0 18 LQ,1 Q4 ?").is_err());
+    }
+
+    #[test]
+    fn test_month_end_policy_round_trips_through_source() {
+        let schedule = Schedule::from_str("0 0 0 29-31 * ?")
+            .unwrap()
+            .with_month_end_policy(MonthEndPolicy::Clamp);
+        assert_eq!("0 0 0 29-31 * ?; month-end=clamp", schedule.source());
+        let parsed = Schedule::from_str(schedule.source()).unwrap();
+        assert_eq!(schedule, parsed);
+        let february = Utc.with_ymd_and_hms(2023, 2, 28, 0, 0, 0).unwrap();
+        assert!(parsed.includes(february));
+        assert_eq!(
+            "0 0 0 29-31 * ?",
+            parsed.with_month_end_policy(MonthEndPolicy::Skip).source()
+        );
+    }
+
+    #[test]
+    fn test_options_are_kept_as_written() {
+        let expression = "@daily ;DST-GAP = Shift-Forward";
+        let schedule = Schedule::from_str(expression).unwrap();
+        assert_eq!(DstGapPolicy::ShiftForward, schedule.dst_gap_policy());
+        assert_eq!(expression, schedule.source());
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        assert!(Schedule::from_str("0 0 * * * ?; dst-gap").is_err());
+        assert!(Schedule::from_str("0 0 * * * ?; dst-gap=sideways").is_err());
+        assert!(Schedule::from_str("0 0 * * * ?; colour=blue").is_err());
+        assert!(Schedule::from_str("0 0 * * * ?;").is_err());
+    }
+
     #[test]
     fn test_no_panic_on_nonexistent_time_after() {
         use chrono::offset::TimeZone;
@@ -0,0 +1,320 @@
+use alloc::vec::Vec;
+use core::iter::{FromIterator, Rev};
+use core::ops::Bound::{self, Excluded, Unbounded};
+use core::ops::RangeBounds;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::clock::Clock;
+use crate::schedule::Schedule;
+use crate::within::{RangeSteps, SearchWithin, Steps};
+
+/// Several [Schedule]s that fire as one, like the two Quartz triggers `0 0/30 9-16 * * ?` and
+/// `0 0 17 * * ?` for a job that runs every half hour from 09:00 to 17:00.
+///
+/// The set fires whenever any of its schedules does, and an instant that several of them match
+/// is only yielded once. It's serialized as the list of its expressions.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{Schedule, ScheduleSet};
+/// use std::str::FromStr;
+///
+/// let set: ScheduleSet = ["0 0/30 9-16 * * ?", "0 0 17 * * ?"]
+///     .iter()
+///     .map(|expression| Schedule::from_str(expression).unwrap())
+///     .collect();
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 16, 0, 0).unwrap();
+/// let fires: Vec<_> = set.after(&start).take(3).collect();
+/// assert_eq!(
+///     vec![
+///         Utc.with_ymd_and_hms(2024, 1, 1, 16, 30, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2024, 1, 1, 17, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).unwrap(),
+///     ],
+///     fires
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScheduleSet {
+    schedules: Vec<Schedule>,
+}
+
+impl ScheduleSet {
+    /// Creates a set of `schedules`.
+    pub fn new(schedules: Vec<Schedule>) -> ScheduleSet {
+        ScheduleSet { schedules }
+    }
+
+    /// Adds `schedule` to the set.
+    pub fn with_schedule(mut self, schedule: Schedule) -> ScheduleSet {
+        self.schedules.push(schedule);
+        self
+    }
+
+    /// Returns the schedules in the set, in the order they were added.
+    pub fn schedules(&self) -> &[Schedule] {
+        &self.schedules
+    }
+
+    /// Returns true if any schedule in the set fires at `date_time`.
+    pub fn includes<D>(&self, date_time: D) -> bool
+    where
+        D: ScheduleDateTime,
+    {
+        self.schedules
+            .iter()
+            .any(|schedule| schedule.includes(date_time.clone()))
+    }
+
+    /// Returns the first DateTime that any schedule in the set matches strictly after `after`.
+    pub fn next_after<D>(&self, after: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.first_within(Excluded(after), Unbounded)
+    }
+
+    /// Returns the last DateTime that any schedule in the set matches strictly before `before`.
+    pub fn prev_before<D>(&self, before: &D) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.last_within(Unbounded, Excluded(before))
+    }
+
+    /// Provides an iterator which will return each DateTime that matches the set starting with
+    /// the current time if applicable.
+    #[cfg(feature = "std")]
+    pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleSetIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+    {
+        self.upcoming_with_clock(timezone, &crate::clock::SystemClock)
+    }
+
+    /// Like the `upcoming` method, but reads the current time from `clock`.
+    pub fn upcoming_with_clock<Z, C>(&self, timezone: Z, clock: &C) -> ScheduleSetIterator<'_, Z>
+    where
+        Z: ScheduleTimeZone,
+        C: Clock + ?Sized,
+    {
+        ScheduleSetIterator {
+            steps: Steps::new(self, timezone.at_utc(&clock.now().naive_utc())),
+        }
+    }
+
+    /// Like the `upcoming` method, but allows you to specify a start time other than the present.
+    pub fn after<D>(&self, after: &D) -> ScheduleSetIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+    {
+        ScheduleSetIterator {
+            steps: Steps::new(self, Some(after.clone())),
+        }
+    }
+
+    /// Provides an iterator over the DateTimes that match the set before `before`, from the
+    /// latest to the earliest.
+    pub fn before<D>(&self, before: &D) -> Rev<ScheduleSetIterator<'_, D::TimeZone>>
+    where
+        D: ScheduleDateTime,
+    {
+        self.after(before).rev()
+    }
+
+    /// Provides an iterator over the DateTimes that match the set within `range`, which is
+    /// interpreted like [Schedule::between].
+    pub fn between<D, R>(&self, range: R) -> ScheduleSetRangeIterator<'_, D::TimeZone>
+    where
+        D: ScheduleDateTime,
+        R: RangeBounds<D>,
+    {
+        ScheduleSetRangeIterator {
+            steps: RangeSteps::new(
+                self,
+                range.start_bound().cloned(),
+                range.end_bound().cloned(),
+            ),
+        }
+    }
+}
+
+impl SearchWithin for ScheduleSet {
+    /// Finds the earliest match of any schedule, so matches shared by several schedules come up
+    /// once.
+    fn first_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.schedules
+            .iter()
+            .filter_map(|schedule| schedule.between((start.cloned(), end.cloned())).next())
+            .min()
+    }
+
+    fn last_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime,
+    {
+        self.schedules
+            .iter()
+            .filter_map(|schedule| schedule.between((start.cloned(), end.cloned())).next_back())
+            .max()
+    }
+}
+
+impl From<Vec<Schedule>> for ScheduleSet {
+    fn from(schedules: Vec<Schedule>) -> ScheduleSet {
+        ScheduleSet::new(schedules)
+    }
+}
+
+impl FromIterator<Schedule> for ScheduleSet {
+    fn from_iter<I>(schedules: I) -> ScheduleSet
+    where
+        I: IntoIterator<Item = Schedule>,
+    {
+        ScheduleSet::new(schedules.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a ScheduleSet {
+    type Item = &'a Schedule;
+    type IntoIter = core::slice::Iter<'a, Schedule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.schedules.iter()
+    }
+}
+
+/// An iterator over the DateTimes of a [ScheduleSet], forwards from a starting point or
+/// backwards with `rev`.
+pub struct ScheduleSetIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    steps: Steps<'a, ScheduleSet, Z>,
+}
+
+impl<Z> Iterator for ScheduleSetIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    type Item = Z::DateTime;
+
+    fn next(&mut self) -> Option<Z::DateTime> {
+        self.steps.next()
+    }
+}
+
+impl<Z> DoubleEndedIterator for ScheduleSetIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps.next_back()
+    }
+}
+
+/// An iterator over the DateTimes of a [ScheduleSet] within a range, from either end.
+pub struct ScheduleSetRangeIterator<'a, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    steps: RangeSteps<'a, ScheduleSet, Z>,
+}
+
+impl<Z> Iterator for ScheduleSetRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    type Item = Z::DateTime;
+
+    fn next(&mut self) -> Option<Z::DateTime> {
+        self.steps.next()
+    }
+}
+
+impl<Z> DoubleEndedIterator for ScheduleSetRangeIterator<'_, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.steps.next_back()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ScheduleSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.schedules.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ScheduleSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<Schedule>::deserialize(deserializer).map(ScheduleSet::new)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use chrono::{TimeZone, Utc};
+    #[cfg(feature = "serde")]
+    use serde_test::{assert_tokens, Token};
+    use std::str::FromStr;
+
+    use super::*;
+    #[cfg(feature = "serde")]
+    use crate::MonthEndPolicy;
+
+    #[test]
+    fn test_shared_matches_are_yielded_once() {
+        let set = ScheduleSet::default()
+            .with_schedule(Schedule::from_str("0 0 * * * ?").unwrap())
+            .with_schedule(Schedule::from_str("0 0 0/2 * * ?").unwrap());
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        assert_eq!(24, set.between(start..end).count());
+        assert_eq!(24, set.between(start..end).rev().count());
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).single(),
+            set.before(&end).next()
+        );
+        assert!(set.includes(end));
+        assert!(!ScheduleSet::default().includes(end));
+        assert_eq!(None, ScheduleSet::default().next_after(&start));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_de_schedule_set_tokens() {
+        let set = ScheduleSet::new(vec![
+            Schedule::from_str("0 0/30 9-16 * * ?").unwrap(),
+            Schedule::from_str("0 0 17 * * ?").unwrap(),
+            Schedule::from_str("0 0 17 31 * ?")
+                .unwrap()
+                .with_month_end_policy(MonthEndPolicy::Clamp),
+        ]);
+        assert_tokens(
+            &set,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::String("0 0/30 9-16 * * ?"),
+                Token::String("0 0 17 * * ?"),
+                Token::String("0 0 17 31 * ?; month-end=clamp"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+}
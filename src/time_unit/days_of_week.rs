@@ -1,9 +1,9 @@
 use crate::error::*;
-use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
 use crate::time_unit::TimeUnitField;
-use once_cell::sync::Lazy;
+use alloc::borrow::Cow;
+use alloc::format;
 use phf::phf_map;
-use std::borrow::Cow;
 
 static DAY_OF_WEEK_MAP: phf::Map<&'static str, Ordinal> = phf_map! {
     "sun" => 1,
@@ -24,15 +24,17 @@ static DAY_OF_WEEK_MAP: phf::Map<&'static str, Ordinal> = phf_map! {
     "saturday" => 7,
 };
 
-static ALL: Lazy<OrdinalSet> = Lazy::new(DaysOfWeek::supported_ordinals);
+static ALL: OrdinalSet<0, 1> = OrdinalSet::inclusive_range(1, 7);
 
 #[derive(Clone, Debug, Eq)]
 pub struct DaysOfWeek {
-    ordinals: Option<OrdinalSet>,
+    ordinals: Option<OrdinalSet<0, 1>>,
 }
 
 impl TimeUnitField for DaysOfWeek {
-    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+    type Set = OrdinalSet<0, 1>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
         DaysOfWeek {
             ordinals: ordinal_set,
         }
@@ -54,11 +56,8 @@ impl TimeUnitField for DaysOfWeek {
                 ErrorKind::Expression(format!("'{}' is not a valid day of the week.", name)).into()
             })
     }
-    fn ordinals(&self) -> &OrdinalSet {
-        match &self.ordinals {
-            Some(ordinal_set) => ordinal_set,
-            None => &ALL,
-        }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
     }
 }
 
@@ -0,0 +1,39 @@
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
+use crate::time_unit::TimeUnitField;
+use alloc::borrow::Cow;
+
+static ALL: OrdinalSet<0, 6> = OrdinalSet::inclusive_range(1, 366);
+
+/// Ordinal days of the year, where January 1st is day 1. Day 366 only exists in leap years.
+#[derive(Clone, Debug, Eq)]
+pub struct DaysOfYear {
+    ordinals: Option<OrdinalSet<0, 6>>,
+}
+
+impl TimeUnitField for DaysOfYear {
+    type Set = OrdinalSet<0, 6>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
+        DaysOfYear {
+            ordinals: ordinal_set,
+        }
+    }
+    fn name() -> Cow<'static, str> {
+        Cow::from("Days of Year")
+    }
+    fn inclusive_min() -> Ordinal {
+        1
+    }
+    fn inclusive_max() -> Ordinal {
+        366
+    }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
+    }
+}
+
+impl PartialEq for DaysOfYear {
+    fn eq(&self, other: &DaysOfYear) -> bool {
+        self.ordinals() == other.ordinals()
+    }
+}
@@ -1,58 +1,63 @@
 mod days_of_month;
 mod days_of_week;
+mod days_of_year;
 mod hours;
 mod minutes;
 mod months;
 mod seconds;
+mod weeks_of_year;
 mod years;
 
 pub use self::days_of_month::DaysOfMonth;
 pub use self::days_of_week::DaysOfWeek;
+pub use self::days_of_year::DaysOfYear;
 pub use self::hours::Hours;
 pub use self::minutes::Minutes;
 pub use self::months::Months;
 pub use self::seconds::Seconds;
+pub use self::weeks_of_year::WeeksOfYear;
 pub use self::years::Years;
 
 use crate::error::*;
-use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::ordinal::{self, Ordinal, OrdinalStore, Ordinals};
 use crate::specifier::{RootSpecifier, Specifier};
-use std::borrow::Cow;
-use std::collections::btree_set;
-use std::iter;
-use std::ops::RangeBounds;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::ToString;
+use core::iter;
+use core::ops::RangeBounds;
 
 pub struct OrdinalIter<'a> {
-    set_iter: btree_set::Iter<'a, Ordinal>,
+    set_iter: ordinal::Iter<'a>,
 }
 
 impl Iterator for OrdinalIter<'_> {
     type Item = Ordinal;
     fn next(&mut self) -> Option<Ordinal> {
-        self.set_iter.next().copied()
+        self.set_iter.next()
     }
 }
 
 impl DoubleEndedIterator for OrdinalIter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.set_iter.next_back().copied()
+        self.set_iter.next_back()
     }
 }
 
 pub struct OrdinalRangeIter<'a> {
-    range_iter: btree_set::Range<'a, Ordinal>,
+    range_iter: ordinal::Iter<'a>,
 }
 
 impl Iterator for OrdinalRangeIter<'_> {
     type Item = Ordinal;
     fn next(&mut self) -> Option<Ordinal> {
-        self.range_iter.next().copied()
+        self.range_iter.next()
     }
 }
 
 impl DoubleEndedIterator for OrdinalRangeIter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.range_iter.next_back().copied()
+        self.range_iter.next_back()
     }
 }
 
@@ -206,17 +211,20 @@ pub trait TimeUnitField
 where
     Self: Sized,
 {
-    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self;
+    /// The set the field stores its ordinals in, sized for the field's range.
+    type Set: OrdinalStore;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self;
     fn name() -> Cow<'static, str>;
     fn inclusive_min() -> Ordinal;
     fn inclusive_max() -> Ordinal;
-    fn ordinals(&self) -> &OrdinalSet;
+    fn ordinals(&self) -> Ordinals<'_>;
 
     fn from_ordinal(ordinal: Ordinal) -> Self {
         Self::from_ordinal_set(iter::once(ordinal).collect())
     }
 
-    fn supported_ordinals() -> OrdinalSet {
+    fn supported_ordinals() -> Self::Set {
         (Self::inclusive_min()..Self::inclusive_max() + 1).collect()
     }
 
@@ -224,7 +232,7 @@ where
         Self::from_optional_ordinal_set(None)
     }
 
-    fn from_ordinal_set(ordinal_set: OrdinalSet) -> Self {
+    fn from_ordinal_set(ordinal_set: Self::Set) -> Self {
         Self::from_optional_ordinal_set(Some(ordinal_set))
     }
 
@@ -237,6 +245,9 @@ where
         ))
         .into())
     }
+    fn ordinals_from_name(name: &str) -> Result<Self::Set, Error> {
+        Ok(iter::once(Self::ordinal_from_name(name)?).collect())
+    }
     fn validate_ordinal(ordinal: Ordinal) -> Result<Ordinal, Error> {
         //println!("validate_ordinal for {} => {}", Self::name(), ordinal);
         match ordinal {
@@ -259,12 +270,12 @@ where
         }
     }
 
-    fn ordinals_from_specifier(specifier: &Specifier) -> Result<OrdinalSet, Error> {
+    fn ordinals_from_specifier(specifier: &Specifier) -> Result<Self::Set, Error> {
         use self::Specifier::*;
         //println!("ordinals_from_specifier for {} => {:?}", Self::name(), specifier);
         match *specifier {
             All => Ok(Self::supported_ordinals()),
-            Point(ordinal) => Ok(([ordinal]).iter().cloned().collect()),
+            Point(ordinal) => Ok(iter::once(Self::validate_ordinal(ordinal)?).collect()),
             Range(start, end) => {
                 match (Self::validate_ordinal(start), Self::validate_ordinal(end)) {
                     (Ok(start), Ok(end)) if start <= end => Ok((start..end + 1).collect()),
@@ -294,7 +305,7 @@ where
         }
     }
 
-    fn ordinals_from_root_specifier(root_specifier: &RootSpecifier) -> Result<OrdinalSet, Error> {
+    fn ordinals_from_root_specifier(root_specifier: &RootSpecifier) -> Result<Self::Set, Error> {
         let ordinals = match root_specifier {
             RootSpecifier::Specifier(specifier) => Self::ordinals_from_specifier(specifier)?,
             RootSpecifier::Period(_, 0) => Err(ErrorKind::Expression(
@@ -311,7 +322,7 @@ where
                     .into());
                 }
 
-                let base_set = match start {
+                let base_set: Self::Set = match start {
                     // A point prior to a period implies a range whose start is the specified
                     // point and terminating inclusively with the inclusive max
                     Specifier::Point(start) => {
@@ -320,12 +331,9 @@ where
                     }
                     specifier => Self::ordinals_from_specifier(specifier)?,
                 };
-                base_set.into_iter().step_by(*step as usize).collect()
+                base_set.view().iter().step_by(*step as usize).collect()
             }
-            RootSpecifier::NamedPoint(ref name) => ([Self::ordinal_from_name(name)?])
-                .iter()
-                .cloned()
-                .collect::<OrdinalSet>(),
+            RootSpecifier::NamedPoint(ref name) => Self::ordinals_from_name(name)?,
         };
         Ok(ordinals)
     }
@@ -1,9 +1,10 @@
 use crate::error::*;
-use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
 use crate::time_unit::TimeUnitField;
-use once_cell::sync::Lazy;
+use alloc::borrow::Cow;
+use alloc::format;
+use core::iter;
 use phf::phf_map;
-use std::borrow::Cow;
 
 static MONTH_MAP: phf::Map<&'static str, Ordinal> = phf_map! {
     "jan" => 1,
@@ -31,15 +32,17 @@ static MONTH_MAP: phf::Map<&'static str, Ordinal> = phf_map! {
     "december" => 12,
 };
 
-static ALL: Lazy<OrdinalSet> = Lazy::new(Months::supported_ordinals);
+static ALL: OrdinalSet<0, 1> = OrdinalSet::inclusive_range(1, 12);
 
 #[derive(Clone, Debug, Eq)]
 pub struct Months {
-    ordinals: Option<OrdinalSet>,
+    ordinals: Option<OrdinalSet<0, 1>>,
 }
 
 impl TimeUnitField for Months {
-    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+    type Set = OrdinalSet<0, 1>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
         Months {
             ordinals: ordinal_set,
         }
@@ -61,11 +64,18 @@ impl TimeUnitField for Months {
                 ErrorKind::Expression(format!("'{}' is not a valid month name.", name)).into()
             })
     }
-    fn ordinals(&self) -> &OrdinalSet {
-        match &self.ordinals {
-            Some(ordinal_set) => ordinal_set,
-            None => &ALL,
-        }
+    fn ordinals_from_name(name: &str) -> Result<Self::Set, Error> {
+        let first_month = match name.to_lowercase().as_ref() {
+            "q1" => 1,
+            "q2" => 4,
+            "q3" => 7,
+            "q4" => 10,
+            _ => return Ok(iter::once(Self::ordinal_from_name(name)?).collect()),
+        };
+        Ok((first_month..first_month + 3).collect())
+    }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
     }
 }
 
@@ -1,17 +1,18 @@
-use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
 use crate::time_unit::TimeUnitField;
-use once_cell::sync::Lazy;
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
-static ALL: Lazy<OrdinalSet> = Lazy::new(Seconds::supported_ordinals);
+static ALL: OrdinalSet<0, 1> = OrdinalSet::inclusive_range(0, 59);
 
 #[derive(Clone, Debug, Eq)]
 pub struct Seconds {
-    ordinals: Option<OrdinalSet>,
+    ordinals: Option<OrdinalSet<0, 1>>,
 }
 
 impl TimeUnitField for Seconds {
-    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+    type Set = OrdinalSet<0, 1>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
         Seconds {
             ordinals: ordinal_set,
         }
@@ -25,11 +26,8 @@ impl TimeUnitField for Seconds {
     fn inclusive_max() -> Ordinal {
         59
     }
-    fn ordinals(&self) -> &OrdinalSet {
-        match &self.ordinals {
-            Some(ordinal_set) => ordinal_set,
-            None => &ALL,
-        }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
     }
 }
 
@@ -0,0 +1,56 @@
+use crate::error::*;
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
+use crate::time_unit::TimeUnitField;
+use alloc::borrow::Cow;
+use alloc::format;
+
+static ALL: OrdinalSet<0, 1> = OrdinalSet::inclusive_range(1, 53);
+
+/// ISO 8601 weeks of the year. Week 1 is the week containing the year's first Thursday, and
+/// weeks start on Monday.
+#[derive(Clone, Debug, Eq)]
+pub struct WeeksOfYear {
+    ordinals: Option<OrdinalSet<0, 1>>,
+}
+
+impl TimeUnitField for WeeksOfYear {
+    type Set = OrdinalSet<0, 1>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
+        WeeksOfYear {
+            ordinals: ordinal_set,
+        }
+    }
+    fn name() -> Cow<'static, str> {
+        Cow::from("Weeks of Year")
+    }
+    fn inclusive_min() -> Ordinal {
+        1
+    }
+    fn inclusive_max() -> Ordinal {
+        53
+    }
+    fn ordinals_from_name(name: &str) -> Result<Self::Set, Error> {
+        let first = match name.to_lowercase().as_ref() {
+            "odd" => 1,
+            "even" => 2,
+            _ => {
+                return Err(ErrorKind::Expression(format!(
+                    "'{}' is not a valid week parity. Use 'odd' or 'even'.",
+                    name
+                ))
+                .into())
+            }
+        };
+        Ok((first..=Self::inclusive_max()).step_by(2).collect())
+    }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
+    }
+}
+
+impl PartialEq for WeeksOfYear {
+    fn eq(&self, other: &WeeksOfYear) -> bool {
+        self.ordinals() == other.ordinals()
+    }
+}
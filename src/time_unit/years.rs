@@ -1,17 +1,18 @@
-use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::ordinal::{Ordinal, OrdinalSet, Ordinals};
 use crate::time_unit::TimeUnitField;
-use once_cell::sync::Lazy;
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
-static ALL: Lazy<OrdinalSet> = Lazy::new(Years::supported_ordinals);
+static ALL: OrdinalSet<1970, 3> = OrdinalSet::inclusive_range(1970, 2100);
 
 #[derive(Clone, Debug, Eq)]
 pub struct Years {
-    ordinals: Option<OrdinalSet>,
+    ordinals: Option<OrdinalSet<1970, 3>>,
 }
 
 impl TimeUnitField for Years {
-    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+    type Set = OrdinalSet<1970, 3>;
+
+    fn from_optional_ordinal_set(ordinal_set: Option<Self::Set>) -> Self {
         Years {
             ordinals: ordinal_set,
         }
@@ -20,19 +21,14 @@ impl TimeUnitField for Years {
         Cow::from("Years")
     }
 
-    // TODO: Using the default impl, this will make a set w/100+ items each time "*" is used.
-    // This is obviously suboptimal.
     fn inclusive_min() -> Ordinal {
         1970
     }
     fn inclusive_max() -> Ordinal {
         2100
     }
-    fn ordinals(&self) -> &OrdinalSet {
-        match &self.ordinals {
-            Some(ordinal_set) => ordinal_set,
-            None => &ALL,
-        }
+    fn ordinals(&self) -> Ordinals<'_> {
+        self.ordinals.as_ref().unwrap_or(&ALL).view()
     }
 }
 
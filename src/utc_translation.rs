@@ -0,0 +1,482 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::RangeBounds;
+use core::str::FromStr;
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+use crate::dst_anomaly::DstAnomaly;
+use crate::ordinal::Ordinal;
+use crate::schedule::{dst_gap_end, DstFoldPolicy, DstGapPolicy, Schedule};
+use crate::time_unit::{TimeUnitField, TimeUnitSpec, Years};
+
+const SECONDS_PER_DAY: i32 = 24 * 60 * 60;
+
+/// The UTC equivalent of a [Schedule] in a time zone, as returned by
+/// [Schedule::to_utc](crate::Schedule::to_utc).
+///
+/// Each DST period gets its own UTC schedules, since the UTC offset decides which hours they
+/// fire at. A period can need more than one schedule when shifting the times to UTC moves some
+/// of them into the next or previous day, or, for offsets that aren't whole hours, into the next
+/// hour. Only the seconds, minutes, hours, days of the month and days of the week are shifted:
+/// the months and years fields are kept as they are, so fires shifted across a month or year
+/// boundary are reported as losses rather than moved into the neighbouring month or year.
+/// Everything a cron expression can't express is listed in [losses](Self::losses); when
+/// there are none, running every schedule during its validity range fires at exactly the same
+/// instants as the original schedule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UtcTranslation {
+    schedules: Vec<UtcSchedule>,
+    losses: Vec<TranslationLoss>,
+}
+
+impl UtcTranslation {
+    /// Returns the UTC schedules, ordered by the start of their validity range.
+    pub fn schedules(&self) -> &[UtcSchedule] {
+        &self.schedules
+    }
+
+    /// Returns where the UTC schedules differ from the original schedule, ordered by kind and
+    /// then by date.
+    pub fn losses(&self) -> &[TranslationLoss] {
+        &self.losses
+    }
+
+    /// Returns true if the UTC schedules fire at exactly the same instants as the original
+    /// schedule.
+    pub fn is_exact(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
+
+/// A UTC [Schedule] that stands in for a schedule in another time zone during one DST period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UtcSchedule {
+    schedule: Schedule,
+    valid_from: DateTime<Utc>,
+    valid_until: DateTime<Utc>,
+    utc_offset_seconds: i32,
+    day_shift: i8,
+}
+
+impl UtcSchedule {
+    /// Returns the schedule, to be evaluated in UTC.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the first instant the schedule stands in for the original one.
+    pub fn valid_from(&self) -> DateTime<Utc> {
+        self.valid_from
+    }
+
+    /// Returns the instant the schedule stops standing in for the original one, which is
+    /// excluded from its validity range.
+    pub fn valid_until(&self) -> DateTime<Utc> {
+        self.valid_until
+    }
+
+    /// Returns the UTC offset of the original time zone during this period.
+    pub fn utc_offset_seconds(&self) -> i32 {
+        self.utc_offset_seconds
+    }
+
+    /// Returns how many days the UTC date of each fire is ahead of its local date, from -1 to 1.
+    /// The days of the month and week are shifted by the same amount.
+    pub fn day_shift(&self) -> i8 {
+        self.day_shift
+    }
+}
+
+/// Where a [UtcTranslation] fires differently from the schedule it was translated from.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TranslationLoss {
+    /// The UTC schedules fire on the UTC date `date` at times the original schedule doesn't.
+    ExtraFires { date: NaiveDate, reason: LossReason },
+    /// The original schedule fires on the UTC date `date` at times the UTC schedules don't.
+    MissedFires { date: NaiveDate, reason: LossReason },
+    /// The schedule's [DstGapPolicy] or [DstFoldPolicy] moves or drops its fire at the wall
+    /// clock time `local`, which the UTC schedules fire at as if both policies were the
+    /// defaults.
+    DstPolicy { local: NaiveDateTime },
+}
+
+/// Why a date of a [UtcTranslation] fires differently from the original schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LossReason {
+    /// Shifting the fires to UTC moves them into another month, and the days of the month
+    /// can't be shifted across months of different lengths.
+    MonthBoundary,
+    /// Shifting the fires to UTC moves them into another year that the years field doesn't
+    /// include, or out of one it does.
+    YearBoundary,
+    /// The schedule restricts its dates in a way a cron expression can't, like weeks or days of
+    /// the year, a [QuarterDay](crate::QuarterDay), an [AnchoredStep](crate::AnchoredStep) or
+    /// [MonthEndPolicy::Clamp](crate::MonthEndPolicy::Clamp).
+    DateFilter,
+}
+
+/// One stretch of time with a single UTC offset, in UTC.
+struct Period {
+    from: NaiveDateTime,
+    until: NaiveDateTime,
+    offset: i32,
+}
+
+/// The fires of one period whose times all shift to UTC in the same way.
+struct Piece {
+    /// The UTC schedule, or `None` if shifting the days of the month leaves none of them.
+    schedule: Option<Schedule>,
+    /// The UTC times of day on their own, for finding the fires on a given date.
+    times: Schedule,
+    day_shift: i8,
+}
+
+pub(crate) fn translate<Z, R>(schedule: &Schedule, timezone: &Z, dates: R) -> UtcTranslation
+where
+    Z: ScheduleTimeZone,
+    R: RangeBounds<NaiveDate>,
+{
+    let first = match dates.start_bound() {
+        Included(date) => Some(*date),
+        Excluded(date) => date.succ_opt(),
+        Unbounded => first_date(schedule),
+    };
+    let last = match dates.end_bound() {
+        Included(date) => Some(*date),
+        Excluded(date) => date.pred_opt(),
+        Unbounded => last_date(schedule),
+    };
+    let mut translation = UtcTranslation {
+        schedules: Vec::new(),
+        losses: Vec::new(),
+    };
+    let (Some(first), Some(last)) = (first, last) else {
+        return translation;
+    };
+    if first > last {
+        return translation;
+    }
+
+    for period in offset_periods(timezone, first, last) {
+        let pieces = pieces(schedule, period.offset);
+        for piece in &pieces {
+            let Some(utc_schedule) = &piece.schedule else {
+                continue;
+            };
+            translation.schedules.push(UtcSchedule {
+                schedule: utc_schedule.clone(),
+                valid_from: Utc.from_utc_datetime(&period.from),
+                valid_until: Utc.from_utc_datetime(&period.until),
+                utc_offset_seconds: period.offset,
+                day_shift: piece.day_shift,
+            });
+        }
+        translation
+            .losses
+            .extend(period_losses(schedule, &period, &pieces));
+    }
+
+    let shifts_forward = schedule.dst_gap_policy() == DstGapPolicy::ShiftForward;
+    let picks_one = schedule.dst_fold_policy() != DstFoldPolicy::Both;
+    if shifts_forward || picks_one {
+        for anomaly in schedule.dst_anomalies(timezone, first..=last) {
+            let applies = match anomaly {
+                DstAnomaly::Skipped { .. } => shifts_forward,
+                DstAnomaly::Repeated { .. } => picks_one,
+            };
+            if applies {
+                translation.losses.push(TranslationLoss::DstPolicy {
+                    local: *anomaly.local(),
+                });
+            }
+        }
+    }
+    translation.losses.sort_unstable();
+    translation.losses.dedup();
+    translation
+}
+
+fn first_date(schedule: &Schedule) -> Option<NaiveDate> {
+    let year = schedule.years().iter().next()?;
+    NaiveDate::from_ymd_opt(year as i32, 1, 1)
+}
+
+fn last_date(schedule: &Schedule) -> Option<NaiveDate> {
+    let year = schedule.years().iter().next_back()?;
+    NaiveDate::from_ymd_opt(year as i32, 12, 31)
+}
+
+/// Returns the first instant of `date`, which is the end of the DST gap if midnight is skipped.
+fn day_start<Z>(timezone: &Z, date: NaiveDate) -> Option<Z::DateTime>
+where
+    Z: ScheduleTimeZone,
+{
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    timezone
+        .at_local(&midnight)
+        .earliest()
+        .or_else(|| dst_gap_end(timezone, &midnight))
+}
+
+fn utc<D>(date_time: &D) -> NaiveDateTime
+where
+    D: ScheduleDateTime,
+{
+    date_time.naive_local() - Duration::seconds(date_time.utc_offset_seconds() as i64)
+}
+
+/// Splits the local dates from `first` to `last` into the stretches of time with one UTC offset.
+///
+/// Offsets are compared at the start of each day, so a transition that is undone on the same
+/// day isn't seen.
+fn offset_periods<Z>(timezone: &Z, first: NaiveDate, last: NaiveDate) -> Vec<Period>
+where
+    Z: ScheduleTimeZone,
+{
+    let mut periods = Vec::new();
+    let Some(mut from) = day_start(timezone, first) else {
+        return periods;
+    };
+    let mut day = from.clone();
+    let mut date = first;
+    while date <= last {
+        let Some(next_date) = date.succ_opt() else {
+            break;
+        };
+        let Some(next_day) = day_start(timezone, next_date) else {
+            break;
+        };
+        if next_day.utc_offset_seconds() != day.utc_offset_seconds() {
+            if let Some(transition) = transition(&day, &next_day) {
+                periods.push(Period {
+                    from: utc(&from),
+                    until: utc(&transition),
+                    offset: from.utc_offset_seconds(),
+                });
+                from = transition;
+            }
+        }
+        day = next_day;
+        date = next_date;
+    }
+    if utc(&from) < utc(&day) {
+        periods.push(Period {
+            from: utc(&from),
+            until: utc(&day),
+            offset: from.utc_offset_seconds(),
+        });
+    }
+    periods
+}
+
+/// Finds the first instant after `before` with the UTC offset of `after`. Transitions happen
+/// on a whole second.
+fn transition<D>(before: &D, after: &D) -> Option<D>
+where
+    D: ScheduleDateTime,
+{
+    let offset = after.utc_offset_seconds();
+    let at = |seconds: i64| before.checked_add_signed(Duration::seconds(seconds));
+    let (mut old, mut new) = (0, (utc(after) - utc(before)).num_seconds());
+    while new - old > 1 {
+        let middle = old + (new - old) / 2;
+        if at(middle)?.utc_offset_seconds() == offset {
+            new = middle;
+        } else {
+            old = middle;
+        }
+    }
+    at(new)
+}
+
+/// Shifts the times of `schedule` to UTC for a period with the UTC offset `offset`.
+///
+/// Shifting a time carries into the next unit when it passes the end of the minute, hour or
+/// day. Within each unit the times that carry and the times that don't are kept apart, so every
+/// piece is a plain set of seconds, minutes and hours, with its own shift of the date.
+fn pieces(schedule: &Schedule, offset: i32) -> Vec<Piece> {
+    let shift = (-offset).rem_euclid(SECONDS_PER_DAY);
+    let base_days = (-offset).div_euclid(SECONDS_PER_DAY);
+    let (hour_shift, minute_shift, second_shift) = (
+        (shift / 3600) as Ordinal,
+        (shift / 60 % 60) as Ordinal,
+        (shift % 60) as Ordinal,
+    );
+    let seconds = schedule.seconds().iter().collect::<Vec<_>>();
+    let minutes = schedule.minutes().iter().collect::<Vec<_>>();
+    let hours = schedule.hours().iter().collect::<Vec<_>>();
+
+    let mut pieces = Vec::new();
+    for second_carry in [false, true] {
+        let shifted_seconds = shift_field(&seconds, second_shift, 60, second_carry);
+        if shifted_seconds.is_empty() {
+            continue;
+        }
+        let minute_shift = minute_shift + second_carry as Ordinal;
+        for minute_carry in [false, true] {
+            let shifted_minutes = shift_field(&minutes, minute_shift, 60, minute_carry);
+            if shifted_minutes.is_empty() {
+                continue;
+            }
+            let hour_shift = hour_shift + minute_carry as Ordinal;
+            for hour_carry in [false, true] {
+                let shifted_hours = shift_field(&hours, hour_shift, 24, hour_carry);
+                if shifted_hours.is_empty() {
+                    continue;
+                }
+                let day_shift = (base_days + hour_carry as i32) as i8;
+                let times = format!(
+                    "{} {} {}",
+                    cron_field(&shifted_seconds, 60),
+                    cron_field(&shifted_minutes, 60),
+                    cron_field(&shifted_hours, 24)
+                );
+                let Ok(times_only) = Schedule::from_str(&format!("{times} * * ?")) else {
+                    continue;
+                };
+                pieces.push(Piece {
+                    schedule: utc_schedule(schedule, &times, day_shift),
+                    times: times_only,
+                    day_shift,
+                });
+            }
+        }
+    }
+    pieces
+}
+
+/// Shifts the `ordinals` of a unit with `size` values by `by`, keeping the ones that carry
+/// into the next unit or the ones that don't.
+fn shift_field(ordinals: &[Ordinal], by: Ordinal, size: Ordinal, carry: bool) -> Vec<Ordinal> {
+    let mut shifted = ordinals
+        .iter()
+        .filter(|&&ordinal| (ordinal + by >= size) == carry)
+        .map(|&ordinal| (ordinal + by) % size)
+        .collect::<Vec<_>>();
+    shifted.sort_unstable();
+    shifted
+}
+
+/// Builds the UTC schedule for the shifted `times`, moving the days of the month and week by
+/// `day_shift`.
+fn utc_schedule(schedule: &Schedule, times: &str, day_shift: i8) -> Option<Schedule> {
+    let days_of_month = if schedule.days_of_month().is_all() {
+        "*".into()
+    } else {
+        let days = schedule
+            .days_of_month()
+            .iter()
+            .map(|day| day as i32 + day_shift as i32)
+            .filter(|day| (1..=31).contains(day))
+            .map(|day| day as Ordinal)
+            .collect::<Vec<_>>();
+        if days.is_empty() {
+            return None;
+        }
+        cron_field(&days, 31)
+    };
+    // Days of the week are numbered from Sunday as 1.
+    let mut days_of_week = schedule
+        .days_of_week()
+        .iter()
+        .map(|day| ((day as i32 - 1 + day_shift as i32).rem_euclid(7) + 1) as Ordinal)
+        .collect::<Vec<_>>();
+    days_of_week.sort_unstable();
+    let months = schedule.months().iter().collect::<Vec<_>>();
+    let years = schedule.years().iter().collect::<Vec<_>>();
+    let expression = format!(
+        "{times} {days_of_month} {} {} {}",
+        cron_field(&months, 12),
+        cron_field(&days_of_week, 7),
+        cron_field(&years, years_in_field()),
+    );
+    Schedule::from_str(&expression).ok()
+}
+
+fn years_in_field() -> usize {
+    (Years::inclusive_max() - Years::inclusive_min() + 1) as usize
+}
+
+/// Writes sorted ordinals as a cron field, joining consecutive runs into ranges.
+fn cron_field(ordinals: &[Ordinal], all: usize) -> String {
+    if ordinals.len() == all {
+        return "*".into();
+    }
+    let mut runs: Vec<(Ordinal, Ordinal)> = Vec::new();
+    for &ordinal in ordinals {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == ordinal => *end = ordinal,
+            _ => runs.push((ordinal, ordinal)),
+        }
+    }
+    let runs = runs
+        .into_iter()
+        .map(|(start, end)| match end - start {
+            0 => format!("{start}"),
+            1 => format!("{start},{end}"),
+            _ => format!("{start}-{end}"),
+        })
+        .collect::<Vec<_>>();
+    runs.join(",")
+}
+
+/// Compares the UTC schedules of a period with the original schedule one UTC date at a time.
+///
+/// The times of each piece are exact, so only whether the piece fires on a date can differ.
+/// That is decided by one fire of the piece on the date, read as a UTC time by the UTC schedule
+/// and as a local time by the original schedule.
+fn period_losses(schedule: &Schedule, period: &Period, pieces: &[Piece]) -> Vec<TranslationLoss> {
+    let mut losses = vec![];
+    let mut date = period.from.date();
+    let last = (period.until - Duration::nanoseconds(1)).date();
+    while date <= last {
+        let (Some(midnight), Some(next_midnight)) = (
+            date.and_hms_opt(0, 0, 0),
+            date.succ_opt().and_then(|next| next.and_hms_opt(0, 0, 0)),
+        ) else {
+            break;
+        };
+        let start = midnight.max(period.from);
+        let end = next_midnight.min(period.until);
+        for piece in pieces {
+            let Some(fire) = piece
+                .times
+                .after_naive(&(start - Duration::nanoseconds(1)))
+                .next()
+                .filter(|fire| *fire < end)
+            else {
+                continue;
+            };
+            let local = fire + Duration::seconds(period.offset as i64);
+            let fires_in_utc = piece
+                .schedule
+                .as_ref()
+                .is_some_and(|utc_schedule| utc_schedule.includes_naive(fire));
+            let fires_locally = schedule.includes_naive(local);
+            if fires_in_utc == fires_locally {
+                continue;
+            }
+            let years = schedule.years();
+            let reason = if years.includes(local.year() as Ordinal)
+                != years.includes(fire.year() as Ordinal)
+            {
+                LossReason::YearBoundary
+            } else if local.month() != fire.month() {
+                LossReason::MonthBoundary
+            } else {
+                LossReason::DateFilter
+            };
+            let loss = if fires_in_utc {
+                TranslationLoss::ExtraFires { date, reason }
+            } else {
+                TranslationLoss::MissedFires { date, reason }
+            };
+            losses.push(loss);
+        }
+        date = next_midnight.date();
+    }
+    losses
+}
@@ -0,0 +1,96 @@
+use core::ops::Bound::{self, Excluded, Unbounded};
+
+use crate::backend::{ScheduleDateTime, ScheduleTimeZone};
+
+/// Schedules built out of other schedules, which find their matches by searching between two
+/// bounds and share their iterators through this.
+pub(crate) trait SearchWithin {
+    /// Returns the earliest match between `start` and `end`.
+    fn first_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime;
+
+    /// Returns the latest match between `start` and `end`.
+    fn last_within<D>(&self, start: Bound<&D>, end: Bound<&D>) -> Option<D>
+    where
+        D: ScheduleDateTime;
+}
+
+/// Steps from one match to the next, forwards from a starting point or backwards from it.
+pub(crate) struct Steps<'a, S, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    schedule: &'a S,
+    previous_datetime: Option<Z::DateTime>,
+}
+
+impl<'a, S, Z> Steps<'a, S, Z>
+where
+    S: SearchWithin,
+    Z: ScheduleTimeZone,
+{
+    /// Steps from `starting_datetime`, or never if there's no start.
+    pub(crate) fn new(schedule: &'a S, starting_datetime: Option<Z::DateTime>) -> Self {
+        Steps {
+            schedule,
+            previous_datetime: starting_datetime,
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Z::DateTime> {
+        let previous = self.previous_datetime.take()?;
+
+        let next = self.schedule.first_within(Excluded(&previous), Unbounded)?;
+        self.previous_datetime = Some(next.clone());
+        Some(next)
+    }
+
+    pub(crate) fn next_back(&mut self) -> Option<Z::DateTime> {
+        let previous = self.previous_datetime.take()?;
+
+        let prev = self.schedule.last_within(Unbounded, Excluded(&previous))?;
+        self.previous_datetime = Some(prev.clone());
+        Some(prev)
+    }
+}
+
+/// Steps through the matches within a range, moving each end inwards as values are taken from it.
+pub(crate) struct RangeSteps<'a, S, Z>
+where
+    Z: ScheduleTimeZone,
+{
+    schedule: &'a S,
+    start: Bound<Z::DateTime>,
+    end: Bound<Z::DateTime>,
+}
+
+impl<'a, S, Z> RangeSteps<'a, S, Z>
+where
+    S: SearchWithin,
+    Z: ScheduleTimeZone,
+{
+    pub(crate) fn new(schedule: &'a S, start: Bound<Z::DateTime>, end: Bound<Z::DateTime>) -> Self {
+        RangeSteps {
+            schedule,
+            start,
+            end,
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Z::DateTime> {
+        let next = self
+            .schedule
+            .first_within(self.start.as_ref(), self.end.as_ref())?;
+        self.start = Excluded(next.clone());
+        Some(next)
+    }
+
+    pub(crate) fn next_back(&mut self) -> Option<Z::DateTime> {
+        let prev = self
+            .schedule
+            .last_within(self.start.as_ref(), self.end.as_ref())?;
+        self.end = Excluded(prev.clone());
+        Some(prev)
+    }
+}
@@ -0,0 +1,301 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use chrono::{DateTime, TimeZone};
+use chrono_tz::Tz;
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+
+use crate::clock::Clock;
+use crate::error::{Error, ErrorKind};
+use crate::schedule::{OwnedScheduleIterator, Schedule, ScheduleIterator};
+
+/// A [Schedule] together with the time zone it's evaluated in.
+///
+/// A `ZonedSchedule` is parsed from a cron expression with an optional `CRON_TZ=` or `TZ=`
+/// prefix naming an IANA time zone, as accepted by cronie and Kubernetes CronJobs. Expressions
+/// without a prefix are evaluated in UTC. The whole expression, prefix included, is kept as the
+/// source and is what gets serialized.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::ZonedSchedule;
+/// use std::str::FromStr;
+///
+/// let standup = ZonedSchedule::from_str("CRON_TZ=America/New_York 0 0 9 * * Mon-Fri").unwrap();
+/// let after = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let next = standup.after(&after).next().unwrap();
+/// assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 14, 0, 0).unwrap(), next);
+/// assert_eq!("America/New_York", standup.timezone().name());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZonedSchedule {
+    source: String,
+    timezone: Tz,
+    schedule: Schedule,
+}
+
+impl ZonedSchedule {
+    /// Combines `schedule` with `timezone`, writing the zone into the source as a `CRON_TZ=`
+    /// prefix.
+    pub fn new(schedule: Schedule, timezone: Tz) -> ZonedSchedule {
+        ZonedSchedule {
+            source: format!("CRON_TZ={} {}", timezone.name(), schedule.source()),
+            timezone,
+            schedule,
+        }
+    }
+
+    /// Returns the [Schedule] without its time zone.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the time zone the schedule is evaluated in.
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Returns a reference to the source expression, including any time zone prefix.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Provides an iterator which will return each DateTime that matches the schedule in its
+    /// time zone, starting with the current time if applicable.
+    #[cfg(feature = "std")]
+    pub fn upcoming(&self) -> ScheduleIterator<'_, Tz> {
+        self.schedule.upcoming(self.timezone)
+    }
+
+    /// The same, but with an iterator with a static ownership
+    #[cfg(feature = "std")]
+    pub fn upcoming_owned(&self) -> OwnedScheduleIterator<Tz> {
+        self.schedule.upcoming_owned(self.timezone)
+    }
+
+    /// Like the `upcoming` method, but reads the current time from `clock`.
+    pub fn upcoming_with_clock<C>(&self, clock: &C) -> ScheduleIterator<'_, Tz>
+    where
+        C: Clock + ?Sized,
+    {
+        self.schedule.upcoming_with_clock(self.timezone, clock)
+    }
+
+    /// The same, but with an iterator with a static ownership
+    pub fn upcoming_owned_with_clock<C>(&self, clock: &C) -> OwnedScheduleIterator<Tz>
+    where
+        C: Clock + ?Sized,
+    {
+        self.schedule
+            .upcoming_owned_with_clock(self.timezone, clock)
+    }
+
+    /// Like the `upcoming` method, but allows you to specify a start time other than the
+    /// present. `after` can be in any time zone.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> ScheduleIterator<'_, Tz>
+    where
+        Z: TimeZone,
+    {
+        self.schedule.after(&after.with_timezone(&self.timezone))
+    }
+
+    /// The same, but with a static ownership.
+    pub fn after_owned<Z>(&self, after: &DateTime<Z>) -> OwnedScheduleIterator<Tz>
+    where
+        Z: TimeZone,
+    {
+        self.schedule
+            .after_owned(after.with_timezone(&self.timezone))
+    }
+
+    /// Returns true if the schedule fires at `date_time`, which can be in any time zone.
+    pub fn includes<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.schedule
+            .includes(date_time.with_timezone(&self.timezone))
+    }
+}
+
+impl TryFrom<Cow<'_, str>> for ZonedSchedule {
+    type Error = Error;
+
+    fn try_from(expression: Cow<'_, str>) -> Result<Self, Self::Error> {
+        let trimmed = expression.trim_start();
+        let prefixed = trimmed
+            .strip_prefix("CRON_TZ=")
+            .or_else(|| trimmed.strip_prefix("TZ="));
+        let (timezone, cron) = match prefixed {
+            Some(rest) => {
+                let (name, cron) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let timezone = name.parse::<Tz>().map_err(|_| {
+                    Error::from(ErrorKind::Expression(format!("Invalid time zone: {name}")))
+                })?;
+                (timezone, cron)
+            }
+            None => (Tz::UTC, trimmed),
+        };
+        let schedule = Schedule::try_from(cron.trim_start())?;
+        Ok(ZonedSchedule {
+            source: expression.into_owned(),
+            timezone,
+            schedule,
+        })
+    }
+}
+
+impl TryFrom<String> for ZonedSchedule {
+    type Error = Error;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        Self::try_from(Cow::Owned(expression))
+    }
+}
+
+impl TryFrom<&str> for ZonedSchedule {
+    type Error = Error;
+
+    fn try_from(expression: &str) -> Result<Self, Self::Error> {
+        Self::try_from(Cow::Borrowed(expression))
+    }
+}
+
+impl FromStr for ZonedSchedule {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        Self::try_from(Cow::Borrowed(expression))
+    }
+}
+
+impl From<ZonedSchedule> for String {
+    fn from(schedule: ZonedSchedule) -> String {
+        schedule.source
+    }
+}
+
+impl Display for ZonedSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ZonedScheduleVisitor;
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> for ZonedScheduleVisitor {
+    type Value = ZonedSchedule;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a valid cron expression with an optional CRON_TZ= prefix")
+    }
+
+    // See the `Schedule` visitor for taking ownership of the `String`.
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ZonedSchedule::try_from(v).map_err(de::Error::custom)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ZonedSchedule::try_from(v).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ZonedSchedule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.source())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ZonedSchedule {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(ZonedScheduleVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use chrono::Utc;
+    #[cfg(feature = "serde")]
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_time_zone_prefixes() {
+        let cron_tz = ZonedSchedule::from_str("CRON_TZ=Europe/Paris 0 30 8 * * *").unwrap();
+        assert_eq!(Tz::Europe__Paris, cron_tz.timezone());
+        assert_eq!("0 30 8 * * *", cron_tz.schedule().source());
+        assert_eq!("CRON_TZ=Europe/Paris 0 30 8 * * *", cron_tz.source());
+
+        let tz = ZonedSchedule::from_str("TZ=Asia/Tokyo  @daily").unwrap();
+        assert_eq!(Tz::Asia__Tokyo, tz.timezone());
+        assert_eq!("@daily", tz.schedule().source());
+
+        let bare = ZonedSchedule::from_str("0 0 * * * *").unwrap();
+        assert_eq!(Tz::UTC, bare.timezone());
+
+        assert!(ZonedSchedule::from_str("CRON_TZ=Mars/Olympus_Mons 0 0 * * * *").is_err());
+        assert!(ZonedSchedule::from_str("CRON_TZ=Europe/Paris").is_err());
+
+        let rebuilt = ZonedSchedule::new(cron_tz.schedule().clone(), Tz::Europe__Paris);
+        assert_eq!(cron_tz, rebuilt);
+
+        let anchor = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let stepped = cron_tz
+            .schedule()
+            .clone()
+            .with_anchored_step(crate::AnchoredStep::weeks(anchor, 2));
+        let rebuilt = ZonedSchedule::new(stepped, Tz::Europe__Paris);
+        assert_eq!(rebuilt, ZonedSchedule::from_str(rebuilt.source()).unwrap());
+    }
+
+    #[test]
+    fn test_zoned_schedule_fires_in_its_time_zone() {
+        let schedule = ZonedSchedule::from_str("TZ=Australia/Sydney 0 0 9 * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 6, 30, 0, 0, 0).unwrap();
+        let next = schedule.after(&after).next().unwrap();
+        assert_eq!("2024-07-01T09:00:00+10:00", next.to_rfc3339());
+        assert!(schedule.includes(&next.with_timezone(&Utc)));
+        assert!(!schedule.includes(&Utc.with_ymd_and_hms(2024, 7, 1, 9, 0, 0).unwrap()));
+        assert_eq!(
+            Tz::Australia__Sydney,
+            schedule.upcoming().next().unwrap().timezone()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_de_zoned_schedule_tokens() {
+        let schedule = ZonedSchedule::from_str("CRON_TZ=America/New_York 0 0 9 * * *").unwrap();
+        assert_tokens(
+            &schedule,
+            &[Token::String("CRON_TZ=America/New_York 0 0 9 * * *")],
+        );
+    }
+}
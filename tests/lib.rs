@@ -2,7 +2,11 @@
 mod tests {
     use chrono::*;
     use chrono_tz::Tz;
-    use cron::{Schedule, TimeUnitSpec};
+    use cron::{
+        AnchoredStep, CompiledSchedule, DstAnomaly, DstFoldPolicy, DstGapPolicy, ExcludingSchedule,
+        LossReason, MockClock, MonthEndPolicy, MultiScheduleIterator, QuarterDay, Schedule,
+        ScheduleIndex, ScheduleSet, TimeUnitSpec, TranslationLoss, UtcTranslation,
+    };
     use std::ops::Bound::{Excluded, Included};
     use std::str::FromStr;
 
@@ -100,6 +104,70 @@ mod tests {
         println!("Previous fire time: {}", prev.to_rfc3339());
     }
 
+    #[test]
+    fn test_next_and_prev_at_a_matching_datetime() {
+        let schedule = Schedule::from_str("0 0 9 * * ? 2024").unwrap();
+        let at = |day, hour, second| Utc.with_ymd_and_hms(2024, 1, day, hour, 0, second).unwrap();
+        let nine = at(2, 9, 0);
+
+        assert_eq!(Some(at(3, 9, 0)), schedule.next_after(&nine));
+        assert_eq!(Some(nine), schedule.next_at_or_after(&nine));
+        assert_eq!(Some(at(1, 9, 0)), schedule.prev_before(&nine));
+        assert_eq!(Some(nine), schedule.prev_at_or_before(&nine));
+        assert_eq!(Some(at(1, 9, 0)), schedule.before(&nine).next());
+
+        // Just past a match, the inclusive and exclusive lookups agree.
+        let past_nine = at(2, 9, 1);
+        assert_eq!(Some(at(3, 9, 0)), schedule.next_at_or_after(&past_nine));
+        assert_eq!(Some(nine), schedule.prev_before(&past_nine));
+        assert_eq!(Some(nine), schedule.prev_at_or_before(&past_nine));
+        assert_eq!(Some(nine), schedule.before(&past_nine).next());
+
+        // Only the inclusive lookups find the first and last matches from the matches themselves.
+        let first = at(1, 9, 0);
+        let last = Utc.with_ymd_and_hms(2024, 12, 31, 9, 0, 0).unwrap();
+        assert_eq!(None, schedule.prev_before(&first));
+        assert_eq!(Some(first), schedule.prev_at_or_before(&first));
+        assert_eq!(None, schedule.before(&first).next());
+        assert_eq!(None, schedule.next_after(&last));
+        assert_eq!(Some(last), schedule.next_at_or_after(&last));
+    }
+
+    #[test]
+    fn test_next_and_prev_across_fall_back() {
+        // 01:30 happens twice in New York on 2024-11-03, first in EDT and then in EST.
+        let schedule = Schedule::from_str("0 30 1 * * ?").unwrap();
+        let timezone = Tz::America__New_York;
+        let (earlier, later) = match timezone.with_ymd_and_hms(2024, 11, 3, 1, 30, 0) {
+            LocalResult::Ambiguous(earlier, later) => (earlier, later),
+            result => panic!("expected a fold, got {result:?}"),
+        };
+        let day_before = timezone.with_ymd_and_hms(2024, 11, 2, 1, 30, 0).unwrap();
+        let day_after = timezone.with_ymd_and_hms(2024, 11, 4, 1, 30, 0).unwrap();
+
+        assert_eq!(Some(later), schedule.next_after(&earlier));
+        assert_eq!(Some(earlier), schedule.next_at_or_after(&earlier));
+        assert_eq!(Some(day_after), schedule.next_after(&later));
+        assert_eq!(Some(later), schedule.next_at_or_after(&later));
+
+        assert_eq!(Some(earlier), schedule.prev_before(&later));
+        assert_eq!(Some(later), schedule.prev_at_or_before(&later));
+        assert_eq!(Some(day_before), schedule.prev_before(&earlier));
+        assert_eq!(Some(earlier), schedule.prev_at_or_before(&earlier));
+
+        let before = schedule.before(&day_after).take(3).collect::<Vec<_>>();
+        assert_eq!(vec![later, earlier, day_before], before);
+        assert_eq!(
+            vec![earlier, day_before],
+            schedule.before(&later).take(2).collect::<Vec<_>>()
+        );
+
+        // Between the two passes, the lookups find the first pass behind and the second ahead.
+        let between = earlier + Duration::minutes(30);
+        assert_eq!(Some(later), schedule.next_at_or_after(&between));
+        assert_eq!(Some(earlier), schedule.prev_at_or_before(&between));
+    }
+
     #[test]
     fn test_yearly() {
         let expression = "@yearly";
@@ -556,6 +624,1642 @@ mod tests {
         assert!(!schedule.includes(not_included));
     }
 
+    #[test]
+    fn test_anchored_step_days_across_months() {
+        let anchor = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let schedule = Schedule::from_str("0 0 0 * * ?")
+            .unwrap()
+            .with_anchored_step(AnchoredStep::days(anchor, 45));
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2024, 2, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 5, 15, 0, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(3).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2023, 11, 17, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2023, 10, 3, 0, 0, 0).unwrap(),
+            ],
+            schedule.after(&start).rev().take(2).collect::<Vec<_>>()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 3, 30, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_anchored_step_months_across_years() {
+        let anchor = NaiveDate::from_ymd_opt(2023, 11, 20).unwrap();
+        let schedule = Schedule::from_str("0 0 0 1 * ?")
+            .unwrap()
+            .with_anchored_step(AnchoredStep::months(anchor, 5));
+        let start = Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2023, 11, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(3).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_odd_weeks_of_year_across_53_week_year() {
+        let schedule = Schedule::from_str("0 0 9 ? * Mon")
+            .unwrap()
+            .with_weeks_of_year("odd")
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 12, 20, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2026, 12, 28, 9, 0, 0).unwrap(), // Week 53
+            Utc.with_ymd_and_hms(2027, 1, 4, 9, 0, 0).unwrap(),   // Week 1
+            Utc.with_ymd_and_hms(2027, 1, 18, 9, 0, 0).unwrap(),  // Week 3
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(3).collect::<Vec<_>>()
+        );
+        assert!(schedule.weeks_of_year().includes(53));
+        assert!(!schedule.weeks_of_year().includes(52));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2027, 1, 11, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_invalid_weeks_of_year() {
+        let schedule = Schedule::from_str("0 0 9 ? * Mon").unwrap();
+        assert!(schedule.clone().with_weeks_of_year("0").is_err());
+        assert!(schedule.with_weeks_of_year("fortnightly").is_err());
+    }
+
+    #[test]
+    fn test_days_of_year_across_leap_year() {
+        let schedule = Schedule::from_str("0 0 0 * * ?")
+            .unwrap()
+            .with_days_of_year("1,91,182,274,366")
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 6, 30, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 9, 30, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(7).collect::<Vec<_>>()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap()));
+        assert_eq!(5, schedule.days_of_year().count());
+        assert!(Schedule::from_str("0 0 0 * * ?")
+            .unwrap()
+            .with_days_of_year("367")
+            .is_err());
+    }
+
+    #[test]
+    fn test_first_weekday_of_quarter() {
+        let schedule = Schedule::from_str("0 0 9 ? * ?")
+            .unwrap()
+            .with_quarter_day(QuarterDay::FirstWeekday);
+        let start = Utc.with_ymd_and_hms(2027, 12, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2028, 1, 3, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2028, 4, 3, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2028, 7, 3, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2028, 10, 2, 9, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2027, 10, 1, 9, 0, 0).unwrap(),
+            schedule.after(&start).next_back().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_last_day_of_quarter() {
+        let schedule = Schedule::from_str("0 0 0 ? Q1,Q4 ?")
+            .unwrap()
+            .with_quarter_day(QuarterDay::Last);
+        let start = Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2023, 12, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(3).collect::<Vec<_>>()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2025, 3, 31, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2025, 6, 30, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_month_end_policy_clamp() {
+        let schedule = Schedule::from_str("0 0 0 31 * ?")
+            .unwrap()
+            .with_month_end_policy(MonthEndPolicy::Clamp);
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 4, 30, 0, 0, 0).unwrap(),
+        ];
+        assert_eq!(
+            expected.to_vec(),
+            schedule.after(&start).take(4).collect::<Vec<_>>()
+        );
+        let end = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            expected.iter().rev().cloned().collect::<Vec<_>>(),
+            schedule.after(&end).rev().take(4).collect::<Vec<_>>()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2024, 4, 30, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 4, 29, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 3, 30, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_month_end_policy_leap_day() {
+        let skip = Schedule::from_str("0 0 0 29 2 ?").unwrap();
+        let clamp = skip.clone().with_month_end_policy(MonthEndPolicy::Clamp);
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap(),
+            skip.after(&start).next().unwrap()
+        );
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2025, 2, 28, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 28, 0, 0, 0).unwrap(),
+            ],
+            clamp.after(&start).take(2).collect::<Vec<_>>()
+        );
+        assert_ne!(skip, clamp);
+    }
+
+    #[test]
+    fn test_between_from_both_ends() {
+        let schedule = Schedule::from_str("0 0 0/6 * * ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+
+        let mut range = schedule.between(start..=end);
+        assert_eq!(Some(start), range.next());
+        assert_eq!(Some(end), range.next_back());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap()),
+            range.next()
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap()),
+            range.next_back()
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()),
+            range.next()
+        );
+        assert_eq!(None, range.next());
+        assert_eq!(None, range.next_back());
+
+        let reversed = schedule
+            .between((Excluded(start), Excluded(end)))
+            .rev()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap(),
+            ],
+            reversed
+        );
+    }
+
+    #[test]
+    fn test_between_unbounded_and_empty() {
+        let schedule = Schedule::from_str("0 0 0 1 1 ? 2030-2032").unwrap();
+        let start = Utc.with_ymd_and_hms(2031, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(2, schedule.between(start..).count());
+        assert_eq!(1, schedule.between(..start).count());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2032, 1, 1, 0, 0, 0).unwrap()),
+            schedule.between(start..).next_back()
+        );
+        assert_eq!(0, schedule.between(start..start).count());
+        assert_eq!(0, schedule.between::<DateTime<Utc>, _>(..).count());
+
+        let leap_day = Schedule::from_str("0 0 0 29 2 ?").unwrap();
+        let end = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(None, leap_day.between(start..end).next());
+    }
+
+    #[test]
+    fn test_between_across_fall_back() {
+        let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+        let timezone: Tz = "America/Chicago".parse().unwrap();
+        let start = timezone.with_ymd_and_hms(2022, 11, 6, 0, 0, 0).unwrap();
+        let end = timezone.with_ymd_and_hms(2022, 11, 6, 3, 0, 0).unwrap();
+        let times = schedule
+            .between(start..end)
+            .map(|dt| dt.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "2022-11-06T00:00:00-05:00",
+                "2022-11-06T01:00:00-05:00",
+                "2022-11-06T01:00:00-06:00",
+                "2022-11-06T02:00:00-06:00",
+            ],
+            times
+        );
+        assert_eq!(4, schedule.between(start..end).rev().count());
+    }
+
+    #[test]
+    fn test_count_between_matches_iteration() {
+        let start = Utc.with_ymd_and_hms(2023, 12, 30, 17, 42, 13).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 3, 2, 5, 10, 0).unwrap();
+        for expression in [
+            "0 */7 9-17 * * Mon-Fri",
+            "30 15 10 1,15,31 * ?",
+            "0 0 0 29 2 ?",
+            "*/20 * 0 * * ?",
+            "0 0 12 1-7 * Mon",
+            "0 0 6 ? * Tue,Thu; weeks-of-year=odd",
+            "0 0 18 LWQ * ?",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(
+                schedule.between(start..end).count() as u64,
+                schedule.count_between(start..end),
+                "{}",
+                expression
+            );
+            assert_eq!(
+                schedule.between((Excluded(start), Included(end))).count() as u64,
+                schedule.count_between((Excluded(start), Included(end))),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_between_across_dst_transitions() {
+        let timezone: Tz = "America/Chicago".parse().unwrap();
+        let start = timezone.with_ymd_and_hms(2022, 3, 10, 12, 0, 0).unwrap();
+        let end = timezone.with_ymd_and_hms(2022, 11, 8, 12, 0, 0).unwrap();
+        for expression in [
+            "0 0/30 * * * ?",
+            "0 30 2 * * ?",
+            "0 0 1 * * ?",
+            "0 30 2 * * ?; dst-gap=shift-forward",
+            "0 30 1 ? * Sun; dst-fold=later-only",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(
+                schedule.between(start..end).count() as u64,
+                schedule.count_between(start..end),
+                "{}",
+                expression
+            );
+        }
+        // 2 AM doesn't exist on the spring forward day and 1 AM happens twice on fall back.
+        let hourly = Schedule::from_str("0 0 * * * ?").unwrap();
+        assert_eq!(243 * 24, hourly.count_between(start..end));
+        // Over whole years the skipped and repeated hours cancel out.
+        let start = timezone.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = timezone.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(2192 * 24, hourly.count_between(start..end));
+    }
+
+    #[test]
+    fn test_count_between_with_a_dst_period_of_two_days() {
+        /// UTC, except for two days of summer time in June 2024.
+        #[derive(Clone, Copy, Debug)]
+        struct ShortDst;
+
+        impl TimeZone for ShortDst {
+            type Offset = FixedOffset;
+
+            fn from_offset(_: &FixedOffset) -> ShortDst {
+                ShortDst
+            }
+
+            fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+                self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+            }
+
+            fn offset_from_local_datetime(
+                &self,
+                local: &NaiveDateTime,
+            ) -> LocalResult<FixedOffset> {
+                let offsets = [0, 3600]
+                    .into_iter()
+                    .map(|seconds| FixedOffset::east_opt(seconds).unwrap())
+                    .filter(|offset| self.offset_from_utc_datetime(&(*local - *offset)) == *offset)
+                    .collect::<Vec<_>>();
+                match offsets[..] {
+                    [] => LocalResult::None,
+                    [offset] => LocalResult::Single(offset),
+                    // Summer time comes first in the fold.
+                    [winter, summer] => LocalResult::Ambiguous(summer, winter),
+                    _ => unreachable!(),
+                }
+            }
+
+            fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+                self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+            }
+
+            fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+                let start = NaiveDate::from_ymd_opt(2024, 6, 11).unwrap();
+                let end = NaiveDate::from_ymd_opt(2024, 6, 13).unwrap();
+                let in_summer = (start.and_hms_opt(0, 0, 0).unwrap()
+                    ..end.and_hms_opt(12, 0, 0).unwrap())
+                    .contains(utc);
+                FixedOffset::east_opt(if in_summer { 3600 } else { 0 }).unwrap()
+            }
+        }
+
+        // The clocks skip from 00:00 to 01:00 on the 11th and go back from 13:00 to 12:00 on
+        // the 13th, well inside June, so the month only counts right if every day is checked.
+        let hourly = Schedule::from_str("0 0 * * * ?").unwrap();
+        let half_past_midnight = Schedule::from_str("0 30 0 * * ?").unwrap();
+        let half_past_noon = Schedule::from_str("0 30 12 * * ?").unwrap();
+        let start = ShortDst.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = ShortDst.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        for schedule in [hourly, half_past_midnight, half_past_noon] {
+            assert_eq!(
+                schedule.between(start..end).count() as u64,
+                schedule.count_between(start..end),
+                "{}",
+                schedule
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_between_matches_iteration_in_every_time_zone() {
+        // Most DST transitions happen around midnight or in the early hours.
+        let schedule = Schedule::from_str("0 30 0-3,23 * * ?").unwrap();
+        for timezone in chrono_tz::TZ_VARIANTS {
+            let start = timezone.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).earliest();
+            let end = timezone.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).earliest();
+            let (Some(start), Some(end)) = (start, end) else {
+                continue;
+            };
+            assert_eq!(
+                schedule.between(start..end).count() as u64,
+                schedule.count_between(start..end),
+                "{}",
+                timezone.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_between_years_of_seconds() {
+        let schedule = Schedule::from_str("* * * * * ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(3653 * 86400, schedule.count_between(start..end));
+        assert_eq!(3653 * 86400 + 1, schedule.count_between(start..=end));
+        assert_eq!(0, schedule.count_between(end..start));
+    }
+
+    #[test]
+    fn test_nth_after_matches_iteration() {
+        let timezone: Tz = "Europe/Berlin".parse().unwrap();
+        let start = timezone.with_ymd_and_hms(2022, 3, 20, 13, 17, 0).unwrap();
+        for expression in [
+            "0 0/15 * * * ?",
+            "0 30 2 * * ?",
+            "*/30 5 1-3 ? * Sun",
+            "0 0 0 29 2 ? 2023-2030",
+            "0 30 2 * * ?; dst-gap=shift-forward",
+            "0 0 12 1-7 * Mon",
+            "0 0 6 ? * Tue,Thu; weeks-of-year=odd",
+            "0 0 18 LWQ * ?",
+            "0 0 0 31 * ?; month-end=clamp",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let expected = schedule.after(&start).take(3000).collect::<Vec<_>>();
+            for n in [0, 1, 17, 95, 96, 97, 500, 2000, 2999] {
+                assert_eq!(
+                    expected.get(n).cloned(),
+                    schedule.nth_after(&start, n as u64),
+                    "{} n={}",
+                    expression,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth_after_far_ahead() {
+        let schedule = Schedule::from_str("* * * * * ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let n = 366 * 86_400 + 3_661;
+        assert_eq!(
+            Utc.with_ymd_and_hms(2025, 1, 1, 1, 1, 2).single(),
+            schedule.nth_after(&start, n)
+        );
+        let last_year = Schedule::from_str("0 0 0 * * ? 2100").unwrap();
+        assert_eq!(None, last_year.nth_after(&start, 365));
+        assert_eq!(
+            Utc.with_ymd_and_hms(2100, 12, 31, 0, 0, 0).single(),
+            last_year.nth_after(&start, 364)
+        );
+    }
+
+    #[test]
+    fn test_compiled_schedule_matches_schedule() {
+        let timezone: Tz = "America/New_York".parse().unwrap();
+        let start = timezone.with_ymd_and_hms(2023, 10, 30, 8, 0, 0).unwrap();
+        let schedules = vec![
+            Schedule::from_str("0 30 1 * * ?").unwrap(),
+            Schedule::from_str("0 0 9 ? * Mon-Fri").unwrap(),
+            Schedule::from_str("0 0 12 13 * Fri").unwrap(),
+            Schedule::from_str("0 0 0 31 * ?")
+                .unwrap()
+                .with_month_end_policy(MonthEndPolicy::Clamp),
+            Schedule::from_str("0 0 9 * * ?")
+                .unwrap()
+                .with_quarter_day(QuarterDay::LastWeekday),
+            Schedule::from_str("0 0 9 ? * Mon")
+                .unwrap()
+                .with_weeks_of_year("even")
+                .unwrap(),
+            Schedule::from_str("0 0 0 29 2 ? 2024-2040").unwrap(),
+        ];
+        for schedule in schedules {
+            let compiled = CompiledSchedule::new(schedule.clone());
+            assert_eq!(&schedule, compiled.schedule());
+            let expected = schedule.after(&start).take(40).collect::<Vec<_>>();
+            assert_eq!(
+                expected,
+                compiled.after(&start).take(40).collect::<Vec<_>>(),
+                "{}",
+                schedule
+            );
+            let end = expected.last().unwrap();
+            assert_eq!(
+                schedule.before(end).take(40).collect::<Vec<_>>(),
+                compiled.before(end).take(40).collect::<Vec<_>>(),
+                "{}",
+                schedule
+            );
+            assert_eq!(
+                schedule.count_between(start..*end),
+                compiled.count_between(start..*end),
+                "{}",
+                schedule
+            );
+            assert_eq!(
+                schedule.nth_after(&start, 500),
+                compiled.nth_after(&start, 500),
+                "{}",
+                schedule
+            );
+            for date_time in &expected {
+                assert!(compiled.includes(*date_time));
+                let next_day = *date_time + Duration::days(1);
+                assert_eq!(
+                    schedule.includes(next_day),
+                    compiled.includes(next_day),
+                    "{}",
+                    schedule
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedule_taken_out_of_a_compiled_schedule_can_be_changed() {
+        let compiled = CompiledSchedule::new(Schedule::from_str("0 0 9 * * ?").unwrap());
+        let changed = compiled.into_schedule().with_quarter_day(QuarterDay::First);
+        let start = Utc.with_ymd_and_hms(2024, 2, 15, 0, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 4, 1, 9, 0, 0).single(),
+            changed.next_after(&start)
+        );
+        let recompiled = CompiledSchedule::new(changed.clone());
+        assert_eq!(changed.next_after(&start), recompiled.next_after(&start));
+        assert_eq!(
+            changed.after_owned(start).take(5).collect::<Vec<_>>(),
+            recompiled.after_owned(start).take(5).collect::<Vec<_>>()
+        );
+    }
+
+    fn batch_schedules() -> Vec<Schedule> {
+        let mut schedules = Vec::new();
+        for minute in 0..60 {
+            schedules.push(Schedule::from_str(&format!("0 {} 9 ? * Mon-Fri", minute)).unwrap());
+            schedules.push(Schedule::from_str(&format!("0 {} 2 * * ?", minute)).unwrap());
+        }
+        schedules.push(Schedule::from_str("0 0 9 ? * Mon-Fri").unwrap());
+        schedules.push(
+            Schedule::from_str("0 0 9 * * ?")
+                .unwrap()
+                .with_quarter_day(QuarterDay::Last),
+        );
+        schedules.push(Schedule::from_str("0 0 9 * * ?").unwrap());
+        schedules.push(Schedule::from_str("0 0 0 1 1 ? 2020").unwrap());
+        schedules
+    }
+
+    #[test]
+    fn test_next_after_each_matches_next_after() {
+        let timezone: Tz = "Europe/London".parse().unwrap();
+        let after = timezone.with_ymd_and_hms(2024, 3, 29, 9, 30, 0).unwrap();
+        let schedules = batch_schedules();
+        let expected = schedules
+            .iter()
+            .map(|schedule| schedule.next_after(&after))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, cron::next_after_each(&schedules, &after));
+        for threads in [1, 3, 64, 1000] {
+            assert_eq!(
+                expected,
+                cron::next_after_each_parallel(
+                    &schedules,
+                    &after,
+                    std::num::NonZeroUsize::new(threads).unwrap()
+                )
+            );
+        }
+        assert!(cron::next_after_each(&[], &after).is_empty());
+    }
+
+    #[test]
+    fn test_schedule_index_matches_includes() {
+        let expressions = [
+            "0 30 9 ? * Mon-Fri",
+            "0 0/15 * * * ?",
+            "*/20 * 9-10 * * ?",
+            "0 0 0 31 * ?",
+            "0 0 12 ? * Sun 2026",
+            "0 0 12 ? * Sun 2027",
+        ];
+        let mut index = ScheduleIndex::new();
+        let mut schedules = Vec::new();
+        for (id, expression) in expressions.iter().enumerate() {
+            let schedule = Schedule::from_str(expression).unwrap();
+            schedules.push((id, schedule.clone()));
+            index.insert(id, schedule);
+        }
+        let clamp = Schedule::from_str("0 0 0 31 * ?")
+            .unwrap()
+            .with_month_end_policy(MonthEndPolicy::Clamp);
+        schedules.push((100, clamp.clone()));
+        index.insert(100, clamp);
+        let odd_weeks = Schedule::from_str("0 30 9 ? * Mon")
+            .unwrap()
+            .with_weeks_of_year("odd")
+            .unwrap();
+        schedules.push((101, odd_weeks.clone()));
+        index.insert(101, odd_weeks);
+        assert_eq!(schedules.len(), index.len());
+
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        for minutes in (0..60 * 24 * 21).step_by(5) {
+            let date_time = start + Duration::minutes(minutes) + Duration::seconds(minutes % 60);
+            let expected = schedules
+                .iter()
+                .filter(|(_, schedule)| schedule.includes(date_time))
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, index.matching(&date_time), "{}", date_time);
+            let expected_minute = schedules
+                .iter()
+                .filter(|(_, schedule)| {
+                    schedule
+                        .after(&(date_time.with_second(0).unwrap() - Duration::seconds(1)))
+                        .next()
+                        .is_some_and(|next| {
+                            next < date_time.with_second(0).unwrap() + Duration::minutes(1)
+                        })
+                })
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                expected_minute,
+                index.matching_minute(&date_time),
+                "{}",
+                date_time
+            );
+        }
+    }
+
+    #[test]
+    fn test_schedule_index_insert_and_remove() {
+        let mut index = ScheduleIndex::new();
+        let nine = Schedule::from_str("0 0 9 * * ?").unwrap();
+        let ten = Schedule::from_str("0 0 10 * * ?").unwrap();
+        let at_nine = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        for id in 0..130 {
+            index.insert(id, nine.clone());
+        }
+        assert_eq!(None, index.insert(200, ten.clone()));
+        assert_eq!(Some(ten.clone()), index.insert(200, nine.clone()));
+        assert_eq!(131, index.matching(&at_nine).len());
+        assert_eq!(Some(nine.clone()), index.remove(&64));
+        assert_eq!(None, index.remove(&64));
+        assert_eq!(None, index.get(&64));
+        index.insert(300, ten);
+        assert_eq!(130, index.matching(&at_nine).len());
+        assert!(!index.matching(&at_nine).contains(&&64));
+        assert!(!index.matching(&at_nine).contains(&&300));
+        assert_eq!(Some(&nine), index.get(&0));
+        assert_eq!(131, index.len());
+    }
+
+    #[test]
+    fn test_multi_schedule_iterator_merges_in_order() {
+        let timezone: Tz = "America/Chicago".parse().unwrap();
+        let schedules = vec![
+            (
+                "quarter hours",
+                Schedule::from_str("0 0/15 1 * * ?").unwrap(),
+            ),
+            ("half past one", Schedule::from_str("0 30 1 * * ?").unwrap()),
+            (
+                "top of the hour",
+                Schedule::from_str("0 0 * * * ?").unwrap(),
+            ),
+        ];
+        // Clocks fall back from 02:00 to 01:00 on this day.
+        let start = timezone.with_ymd_and_hms(2023, 11, 5, 0, 50, 0).unwrap();
+        let end = timezone.with_ymd_and_hms(2023, 11, 5, 2, 0, 0).unwrap();
+        let merged = MultiScheduleIterator::after(schedules.clone(), &start)
+            .take_while(|(_, date_time)| *date_time <= end)
+            .map(|(id, date_time)| (id, date_time.to_rfc3339()))
+            .collect::<Vec<_>>();
+        let expected = [
+            ("quarter hours", "2023-11-05T01:00:00-05:00"),
+            ("top of the hour", "2023-11-05T01:00:00-05:00"),
+            ("quarter hours", "2023-11-05T01:15:00-05:00"),
+            ("quarter hours", "2023-11-05T01:30:00-05:00"),
+            ("half past one", "2023-11-05T01:30:00-05:00"),
+            ("quarter hours", "2023-11-05T01:45:00-05:00"),
+            ("quarter hours", "2023-11-05T01:00:00-06:00"),
+            ("top of the hour", "2023-11-05T01:00:00-06:00"),
+            ("quarter hours", "2023-11-05T01:15:00-06:00"),
+            ("quarter hours", "2023-11-05T01:30:00-06:00"),
+            ("half past one", "2023-11-05T01:30:00-06:00"),
+            ("quarter hours", "2023-11-05T01:45:00-06:00"),
+            ("top of the hour", "2023-11-05T02:00:00-06:00"),
+        ]
+        .iter()
+        .map(|&(id, date_time)| (id, date_time.to_owned()))
+        .collect::<Vec<_>>();
+        assert_eq!(expected, merged);
+
+        let after_end = end + Duration::seconds(1);
+        let mut reversed = MultiScheduleIterator::after(schedules, &after_end)
+            .rev()
+            .take_while(|(_, date_time)| *date_time > start)
+            .map(|(id, date_time)| (id, date_time.to_rfc3339()))
+            .collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(expected, reversed);
+    }
+
+    #[test]
+    fn test_multi_schedule_iterator_ends_with_the_last_schedule() {
+        let schedules = vec![
+            (1, Schedule::from_str("0 0 0 1 1 ? 2024").unwrap()),
+            (2, Schedule::from_str("0 0 0 1 1 ? 2025-2026").unwrap()),
+            (3, Schedule::from_str("0 0 0 1 1 ? 2020").unwrap()),
+        ];
+        let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let ids = MultiScheduleIterator::after(schedules, &start)
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 2], ids);
+        let empty: Vec<(u8, Schedule)> = Vec::new();
+        assert_eq!(None, MultiScheduleIterator::after(empty, &start).next());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_backend_matches_chrono() {
+        use cron::OffsetZone;
+        use time::{OffsetDateTime, UtcOffset};
+
+        let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+        let to_time = |date_time: DateTime<FixedOffset>| {
+            let nanos = date_time.timestamp_nanos_opt().unwrap() as i128;
+            OffsetDateTime::from_unix_timestamp_nanos(nanos)
+                .unwrap()
+                .to_offset(offset)
+        };
+        let chrono_start = FixedOffset::east_opt(5 * 3600 + 30 * 60)
+            .unwrap()
+            .with_ymd_and_hms(2024, 2, 28, 10, 0, 0)
+            .unwrap();
+        let time_start = to_time(chrono_start);
+        let chrono_end = chrono_start + Duration::days(7);
+        let time_end = to_time(chrono_end);
+
+        for expression in [
+            "0 0/15 * * * *",
+            "0 30 9 ? * Mon-Fri",
+            "0 0 12 29 2 ? *",
+            "0 0 0 1 1 ? 2020",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let expected = schedule
+                .after(&chrono_start)
+                .take(20)
+                .map(to_time)
+                .collect::<Vec<_>>();
+            let actual = schedule.after(&time_start).take(20).collect::<Vec<_>>();
+            assert_eq!(expected, actual, "after, {}", expression);
+            assert!(actual.iter().all(|date_time| date_time.offset() == offset));
+            for date_time in &actual {
+                assert!(schedule.includes(*date_time), "includes, {}", expression);
+            }
+
+            let expected = schedule
+                .before(&chrono_start)
+                .take(20)
+                .map(to_time)
+                .collect::<Vec<_>>();
+            let actual = schedule.before(&time_start).take(20).collect::<Vec<_>>();
+            assert_eq!(expected, actual, "before, {}", expression);
+
+            assert_eq!(
+                schedule.count_between(chrono_start..chrono_end),
+                schedule.count_between(time_start..time_end),
+                "count_between, {}",
+                expression
+            );
+        }
+
+        let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+        let next = schedule.upcoming(OffsetZone(offset)).next().unwrap();
+        assert_eq!(offset, next.offset());
+
+        let clock = MockClock::new(Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            None,
+            schedule
+                .upcoming_with_clock(OffsetZone(offset), &clock)
+                .next()
+        );
+        assert_eq!(
+            None,
+            schedule
+                .upcoming_owned_with_clock(OffsetZone(offset), &clock)
+                .next()
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_backend_beyond_chrono_range() {
+        use time::macros::datetime;
+        use time::{Date, Month, Time};
+
+        // The dev-dependency enables time's `large-dates`, so these are out of chrono's range.
+        let schedule = Schedule::from_str("0 0 0 * * ?").unwrap();
+        let far_ahead = Date::from_calendar_date(300000, Month::January, 1)
+            .unwrap()
+            .midnight()
+            .assume_utc();
+        let far_behind = Date::from_calendar_date(-300000, Month::January, 1)
+            .unwrap()
+            .midnight()
+            .assume_utc();
+        assert!(!schedule.includes(far_ahead));
+        assert_eq!(None, schedule.after(&far_ahead).next());
+        assert_eq!(
+            Some(datetime!(2100-12-31 0:00 UTC)),
+            schedule.before(&far_ahead).next()
+        );
+        assert!(!schedule.includes(far_behind));
+        assert_eq!(
+            Some(datetime!(1970-01-01 0:00 UTC)),
+            schedule.after(&far_behind).next()
+        );
+        assert_eq!(None, schedule.before(&far_behind).next());
+
+        // The end of the range has no wall clock time at +01:00.
+        let start = datetime!(2100-12-30 12:00 +1);
+        let end = Date::MAX
+            .with_time(Time::from_hms(23, 59, 59).unwrap())
+            .assume_utc();
+        assert_eq!(
+            Some(datetime!(2100-12-31 0:00 UTC)),
+            schedule.between(start..end).next_back()
+        );
+        assert_eq!(1, schedule.count_between(start..end));
+        assert_eq!(
+            schedule.count_between(datetime!(1970-01-01 0:00 UTC)..end),
+            schedule.count_between(far_behind..far_ahead)
+        );
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_jiff_backend_matches_chrono_across_dst() {
+        use jiff::Zoned;
+
+        let to_rfc3339 = |zoned: Zoned| zoned.timestamp().to_string();
+        let chrono_rfc3339 = |date_time: DateTime<Tz>| {
+            date_time
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        };
+        let chrono_start = Tz::America__New_York
+            .with_ymd_and_hms(2024, 3, 9, 0, 0, 0)
+            .unwrap();
+        let jiff_start = jiff::civil::date(2024, 3, 9)
+            .in_tz("America/New_York")
+            .unwrap();
+        let chrono_fall = Tz::America__New_York
+            .with_ymd_and_hms(2024, 11, 4, 0, 0, 0)
+            .unwrap();
+        let jiff_fall = jiff::civil::date(2024, 11, 4)
+            .in_tz("America/New_York")
+            .unwrap();
+
+        for expression in ["0 0/30 * * * *", "0 30 2 * * ?", "0 15 1 * * ?"] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let expected = schedule
+                .after(&chrono_start)
+                .take(60)
+                .map(chrono_rfc3339)
+                .collect::<Vec<_>>();
+            let actual = schedule
+                .after(&jiff_start)
+                .take(60)
+                .map(to_rfc3339)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, actual, "after, {}", expression);
+
+            let expected = schedule
+                .before(&chrono_fall)
+                .take(60)
+                .map(chrono_rfc3339)
+                .collect::<Vec<_>>();
+            let actual = schedule
+                .before(&jiff_fall)
+                .take(60)
+                .map(to_rfc3339)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, actual, "before, {}", expression);
+
+            assert_eq!(
+                schedule.count_between(chrono_start..chrono_fall),
+                schedule.count_between(jiff_start.clone()..jiff_fall.clone()),
+                "count_between, {}",
+                expression
+            );
+        }
+
+        let schedule = Schedule::from_str("0 30 1 * * ?").unwrap();
+        let fold = jiff::civil::date(2024, 11, 3)
+            .at(1, 30, 0, 0)
+            .in_tz("America/New_York")
+            .unwrap();
+        assert!(schedule.includes(fold.clone()));
+        assert_eq!(
+            "America/New_York",
+            schedule
+                .next_after(&fold)
+                .unwrap()
+                .time_zone()
+                .iana_name()
+                .unwrap()
+        );
+
+        let zone = cron::JiffZone(jiff::tz::TimeZone::get("America/New_York").unwrap());
+        let clock = MockClock::new(Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(None, schedule.upcoming_with_clock(zone, &clock).next());
+    }
+
+    #[test]
+    fn test_after_naive_ignores_dst() {
+        let schedule = Schedule::from_str("0 0/30 1-2 * * ?").unwrap();
+        let spring_forward = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let expected = ["01:00:00", "01:30:00", "02:00:00", "02:30:00"]
+            .iter()
+            .map(|time| format!("2024-03-10 {}", time))
+            .collect::<Vec<_>>();
+        let fires = schedule
+            .after_naive(&spring_forward)
+            .take(4)
+            .map(|date_time| date_time.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, fires);
+
+        // Naive times are the same as UTC times with the offset dropped.
+        let utc = Utc.from_utc_datetime(&spring_forward);
+        let from_utc = schedule
+            .after(&utc)
+            .take(50)
+            .map(|date_time| date_time.naive_utc())
+            .collect::<Vec<_>>();
+        let naive = schedule
+            .after_naive(&spring_forward)
+            .take(50)
+            .collect::<Vec<_>>();
+        assert_eq!(from_utc, naive);
+
+        let last = *naive.last().unwrap();
+        let mut reversed = schedule
+            .after_naive(&last)
+            .rev()
+            .take(49)
+            .collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(naive[..49], reversed[..]);
+
+        assert!(schedule.includes_naive(spring_forward + Duration::minutes(150)));
+        assert!(!schedule.includes_naive(spring_forward + Duration::minutes(180)));
+    }
+
+    #[test]
+    fn test_dst_gap_policy_shift_forward() {
+        let schedule = Schedule::from_str("0 0/20 2 * * ?")
+            .unwrap()
+            .with_dst_gap_policy(DstGapPolicy::ShiftForward);
+        let start = Tz::America__New_York
+            .with_ymd_and_hms(2024, 3, 9, 3, 0, 0)
+            .unwrap();
+        let expected = [
+            "2024-03-10T03:00:00-04:00",
+            "2024-03-11T02:00:00-04:00",
+            "2024-03-11T02:20:00-04:00",
+        ];
+        let fires = schedule
+            .after(&start)
+            .take(3)
+            .map(|date_time| date_time.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(expected.to_vec(), fires);
+
+        let end = Tz::America__New_York
+            .with_ymd_and_hms(2024, 3, 11, 2, 30, 0)
+            .unwrap();
+        let mut reversed = schedule
+            .before(&end)
+            .take(3)
+            .map(|date_time| date_time.to_rfc3339())
+            .collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(expected.to_vec(), reversed);
+        assert_eq!(3, schedule.count_between(start..end));
+
+        let gap_end = Tz::America__New_York
+            .with_ymd_and_hms(2024, 3, 10, 3, 0, 0)
+            .unwrap();
+        assert!(schedule.includes(gap_end));
+        assert!(!schedule.includes(gap_end + Duration::seconds(1)));
+        let skipping = schedule.clone().with_dst_gap_policy(DstGapPolicy::Skip);
+        assert!(!skipping.includes(gap_end));
+        assert_eq!(2, skipping.count_between(start..end));
+
+        let mut index = ScheduleIndex::new();
+        index.insert("shifted", schedule);
+        index.insert("skipping", skipping);
+        assert_eq!(vec![&"shifted"], index.matching(&gap_end));
+        assert_eq!(
+            vec![&"shifted"],
+            index.matching_minute(&(gap_end + Duration::seconds(30)))
+        );
+    }
+
+    #[test]
+    fn test_dst_fold_policy() {
+        let start = Tz::America__New_York
+            .with_ymd_and_hms(2024, 11, 3, 0, 0, 0)
+            .unwrap();
+        let end = Tz::America__New_York
+            .with_ymd_and_hms(2024, 11, 3, 3, 0, 0)
+            .unwrap();
+        let cases = [
+            (
+                DstFoldPolicy::Both,
+                vec![
+                    "2024-11-03T01:00:00-04:00",
+                    "2024-11-03T01:30:00-04:00",
+                    "2024-11-03T01:00:00-05:00",
+                    "2024-11-03T01:30:00-05:00",
+                    "2024-11-03T02:00:00-05:00",
+                    "2024-11-03T02:30:00-05:00",
+                ],
+            ),
+            (
+                DstFoldPolicy::EarlierOnly,
+                vec![
+                    "2024-11-03T01:00:00-04:00",
+                    "2024-11-03T01:30:00-04:00",
+                    "2024-11-03T02:00:00-05:00",
+                    "2024-11-03T02:30:00-05:00",
+                ],
+            ),
+            (
+                DstFoldPolicy::LaterOnly,
+                vec![
+                    "2024-11-03T01:00:00-05:00",
+                    "2024-11-03T01:30:00-05:00",
+                    "2024-11-03T02:00:00-05:00",
+                    "2024-11-03T02:30:00-05:00",
+                ],
+            ),
+        ];
+        for (policy, expected) in cases {
+            let schedule = Schedule::from_str("0 0/30 1-2 * * ?")
+                .unwrap()
+                .with_dst_fold_policy(policy);
+            let fires = schedule
+                .between(start..end)
+                .map(|date_time| date_time.to_rfc3339())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, fires, "{:?}", policy);
+            let mut reversed = schedule
+                .between(start..end)
+                .rev()
+                .map(|date_time| date_time.to_rfc3339())
+                .collect::<Vec<_>>();
+            reversed.reverse();
+            assert_eq!(expected, reversed, "{:?}", policy);
+            assert_eq!(
+                expected.len() as u64,
+                schedule.count_between(start..end),
+                "{:?}",
+                policy
+            );
+
+            // Starting inside the fold still finds the rest of it.
+            let first_pass = Tz::America__New_York
+                .with_ymd_and_hms(2024, 11, 3, 1, 10, 0)
+                .earliest()
+                .unwrap();
+            let after_first_pass = schedule
+                .after(&first_pass)
+                .take_while(|date_time| *date_time < end)
+                .map(|date_time| date_time.to_rfc3339())
+                .collect::<Vec<_>>();
+            let expected_after = expected
+                .iter()
+                .filter(|fire| DateTime::parse_from_rfc3339(fire).unwrap() > first_pass)
+                .cloned()
+                .collect::<Vec<_>>();
+            assert_eq!(expected_after, after_first_pass, "{:?}", policy);
+
+            let mut index = ScheduleIndex::new();
+            index.insert(policy, schedule.clone());
+            let mut instant = start;
+            while instant < end {
+                let fires = expected.contains(&instant.to_rfc3339().as_str());
+                assert_eq!(fires, schedule.includes(instant), "{}", instant);
+                assert_eq!(fires, !index.matching(&instant).is_empty(), "{}", instant);
+                instant += Duration::minutes(30);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dst_anomalies_at_midnight_transitions() {
+        // Santiago changes its clocks at midnight, so a gap starts a day and a fold ends one.
+        let schedule = Schedule::from_str("0 0/30 * * * ?").unwrap();
+        let santiago = Tz::America__Santiago;
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let anomalies = schedule
+            .dst_anomalies(&santiago, start..=end)
+            .into_iter()
+            .map(|anomaly| {
+                let kind = match anomaly {
+                    DstAnomaly::Skipped { .. } => "skipped",
+                    DstAnomaly::Repeated { .. } => "repeated",
+                };
+                (
+                    kind,
+                    anomaly.local().to_string(),
+                    anomaly.earlier().to_rfc3339(),
+                    anomaly.later().to_rfc3339(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [
+            (
+                "repeated",
+                "2024-04-06 23:00:00",
+                "2024-04-06T23:00:00-03:00",
+                "2024-04-06T23:00:00-04:00",
+            ),
+            (
+                "repeated",
+                "2024-04-06 23:30:00",
+                "2024-04-06T23:30:00-03:00",
+                "2024-04-06T23:30:00-04:00",
+            ),
+            (
+                "skipped",
+                "2024-09-08 00:00:00",
+                "2024-09-07T23:00:00-04:00",
+                "2024-09-08T01:00:00-03:00",
+            ),
+            (
+                "skipped",
+                "2024-09-08 00:30:00",
+                "2024-09-07T23:30:00-04:00",
+                "2024-09-08T01:30:00-03:00",
+            ),
+        ]
+        .iter()
+        .map(|&(kind, local, earlier, later)| {
+            (kind, local.to_owned(), earlier.to_owned(), later.to_owned())
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(expected, anomalies);
+
+        // Ranges are local dates, so excluding the days of the transitions drops them.
+        let gap_day = NaiveDate::from_ymd_opt(2024, 9, 8).unwrap();
+        assert_eq!(2, schedule.dst_anomalies(&santiago, start..gap_day).len());
+        assert_eq!(2, schedule.dst_anomalies(&santiago, gap_day..end).len());
+        assert!(schedule.dst_anomalies(&Utc, start..=end).is_empty());
+    }
+
+    fn translated_fires(translation: &UtcTranslation) -> Vec<DateTime<Utc>> {
+        let mut fires = translation
+            .schedules()
+            .iter()
+            .flat_map(|utc| {
+                let start = utc.valid_from() - Duration::nanoseconds(1);
+                utc.schedule()
+                    .after(&start)
+                    .take_while(move |fire| *fire < utc.valid_until())
+            })
+            .collect::<Vec<_>>();
+        fires.sort();
+        fires
+    }
+
+    #[test]
+    fn test_to_utc_fires_at_the_same_instants() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let cases = [
+            // A half hour offset carries some of the minutes into the next hour.
+            ("0 0/20 9 ? * Mon-Fri", Tz::Asia__Kolkata, 2),
+            // Transitions at midnight, with times on both sides of the UTC date line.
+            ("0 15 0,1,21,23 ? * Sun,Wed", Tz::America__Santiago, 6),
+            ("30 45 1,2,3 * * ?", Tz::Europe__London, 3),
+        ];
+        for (expression, timezone, count) in cases {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let translation = schedule.to_utc(&timezone, start..=end);
+            assert!(translation.is_exact(), "{expression}");
+            assert_eq!(count, translation.schedules().len(), "{expression}");
+
+            let from = timezone.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            let until = timezone.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+            let expected = schedule
+                .after(&(from - Duration::nanoseconds(1)))
+                .take_while(|fire| *fire < until)
+                .map(|fire| fire.with_timezone(&Utc))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, translated_fires(&translation), "{expression}");
+        }
+
+        let kolkata = Schedule::from_str("0 0/20 9 ? * Mon-Fri")
+            .unwrap()
+            .to_utc(&Tz::Asia__Kolkata, start..=end);
+        let sources = kolkata
+            .schedules()
+            .iter()
+            .map(|utc| utc.schedule().source())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["0 30,50 3 * * 2-6 *", "0 10 4 * * 2-6 *"], sources);
+        assert_eq!(19800, kolkata.schedules()[0].utc_offset_seconds());
+        assert_eq!(0, kolkata.schedules()[0].day_shift());
+    }
+
+    #[test]
+    fn test_to_utc_reports_month_boundaries() {
+        // 08:00 on the 1st in Tokyo is 23:00 on the last day of the previous month in UTC.
+        let schedule = Schedule::from_str("0 0 8 1 * ?").unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let translation = schedule.to_utc(&Tz::Asia__Tokyo, start..=end);
+        assert!(translation.schedules().is_empty());
+        let missed = translation
+            .losses()
+            .iter()
+            .map(|loss| match loss {
+                TranslationLoss::MissedFires { date, reason } => (date.to_string(), *reason),
+                loss => panic!("unexpected loss {loss:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(12, missed.len());
+        assert_eq!(
+            ("2023-12-31".to_string(), LossReason::MonthBoundary),
+            missed[0]
+        );
+        assert_eq!(
+            ("2024-02-29".to_string(), LossReason::MonthBoundary),
+            missed[2]
+        );
+
+        // Shifting the 30th to the 31st misses the months with 30 days.
+        let schedule = Schedule::from_str("0 0 20 30 * ? 2024").unwrap();
+        let translation = schedule.to_utc(&Tz::America__Los_Angeles, start..=end);
+        let sources = translation
+            .schedules()
+            .iter()
+            .map(|utc| utc.schedule().source())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "0 0 4 31 * * 2024",
+                "0 0 3 31 * * 2024",
+                "0 0 4 31 * * 2024"
+            ],
+            sources
+        );
+        let missed = [5, 7, 10, 12]
+            .into_iter()
+            .map(|month| TranslationLoss::MissedFires {
+                date: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                reason: LossReason::MonthBoundary,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(missed, translation.losses());
+
+        // Weeks of the year can't be written in a cron expression.
+        let schedule = Schedule::from_str("0 0 12 ? * Mon")
+            .unwrap()
+            .with_weeks_of_year("1-2")
+            .unwrap();
+        let january = schedule.to_utc(&Utc, start..NaiveDate::from_ymd_opt(2024, 2, 1).unwrap());
+        assert_eq!(
+            [15, 22, 29]
+                .into_iter()
+                .map(|day| TranslationLoss::ExtraFires {
+                    date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+                    reason: LossReason::DateFilter,
+                })
+                .collect::<Vec<_>>(),
+            january.losses()
+        );
+    }
+
+    #[test]
+    fn test_to_utc_reports_dst_policies() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let schedule = Schedule::from_str("0 30 1,2 * * ?").unwrap();
+        assert!(schedule
+            .to_utc(&Tz::America__New_York, start..=end)
+            .is_exact());
+
+        let schedule = schedule
+            .with_dst_gap_policy(DstGapPolicy::ShiftForward)
+            .with_dst_fold_policy(DstFoldPolicy::EarlierOnly);
+        let translation = schedule.to_utc(&Tz::America__New_York, start..=end);
+        let locals = translation
+            .losses()
+            .iter()
+            .map(|loss| match loss {
+                TranslationLoss::DstPolicy { local } => local.to_string(),
+                loss => panic!("unexpected loss {loss:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["2024-03-10 02:30:00", "2024-11-03 01:30:00"], locals);
+    }
+
+    #[test]
+    fn test_upcoming_with_mock_clock() {
+        let schedule = Schedule::from_str("0 0/15 * * * ?").unwrap();
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 3, 10, 6, 50, 0).unwrap());
+        let fires = schedule
+            .upcoming_with_clock(Tz::America__New_York, &clock)
+            .take(2)
+            .map(|fire| fire.to_rfc3339())
+            .collect::<Vec<_>>();
+        // 02:00 is skipped as the clocks spring forward.
+        assert_eq!(
+            vec!["2024-03-10T03:00:00-04:00", "2024-03-10T03:15:00-04:00"],
+            fires
+        );
+
+        clock.advance(Duration::minutes(40));
+        let mut owned = schedule.upcoming_owned_with_clock(Tz::America__New_York, &clock);
+        assert_eq!(
+            "2024-03-10T03:45:00-04:00",
+            owned.next().unwrap().to_rfc3339()
+        );
+
+        clock.set(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        let next = schedule.upcoming_with_clock(Utc, &clock).next();
+        assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 0, 15, 0).single(), next);
+    }
+
+    #[test]
+    fn test_mock_clock_shared_between_threads() {
+        let schedule = Schedule::from_str("0 0 * * * ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap();
+        let clock = MockClock::new(start);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| clock.advance(Duration::hours(1)));
+            }
+        });
+        assert_eq!(MockClock::new(start + Duration::hours(4)), clock);
+        let next = std::thread::scope(|scope| {
+            scope
+                .spawn(|| schedule.upcoming_with_clock(Utc, &clock).next())
+                .join()
+                .unwrap()
+        });
+        assert_eq!(Utc.with_ymd_and_hms(2024, 1, 1, 5, 0, 0).single(), next);
+        assert_eq!(clock, clock.clone());
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_zoned_schedule_upcoming() {
+        let schedule = cron::ZonedSchedule::from_str("CRON_TZ=Europe/London 0 30 1 * * ?").unwrap();
+        // 01:30 is skipped in London as the clocks spring forward on 2024-03-31.
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap());
+        let fires = schedule
+            .upcoming_with_clock(&clock)
+            .take(2)
+            .map(|fire| fire.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec!["2024-04-01T01:30:00+01:00", "2024-04-02T01:30:00+01:00"],
+            fires
+        );
+
+        clock.advance(Duration::days(3));
+        let mut owned = schedule.upcoming_owned_with_clock(&clock);
+        assert_eq!(
+            "2024-04-03T01:30:00+01:00",
+            owned.next().unwrap().to_rfc3339()
+        );
+        assert_eq!(
+            "2024-04-02T01:30:00+01:00",
+            owned.next_back().unwrap().to_rfc3339()
+        );
+
+        let now = Utc::now();
+        let next = schedule.upcoming().next().unwrap();
+        assert_eq!(Tz::Europe__London, next.timezone());
+        assert!(next > now);
+        assert!(next.with_timezone(&Utc) - now <= Duration::days(1) + Duration::hours(1));
+        assert_eq!(Some(next), schedule.upcoming_owned().next());
+    }
+
+    #[cfg(all(feature = "chrono-tz", feature = "serde"))]
+    #[test]
+    fn test_zoned_schedule_serde() {
+        use cron::ZonedSchedule;
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        let source = "TZ=Asia/Kolkata 0 0 9 * * Mon-Fri";
+        let schedule = ZonedSchedule::from_str(source).unwrap();
+        assert_tokens(&schedule, &[Token::String(source)]);
+        assert_tokens(&schedule, &[Token::Str(source)]);
+
+        let serialized = postcard::to_stdvec(&schedule).unwrap();
+        let deserialized: ZonedSchedule = postcard::from_bytes(&serialized).unwrap();
+        assert_eq!(schedule, deserialized);
+        assert_eq!(source, deserialized.source());
+        assert_eq!(Tz::Asia__Kolkata, deserialized.timezone());
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            schedule.after(&start).take(5).collect::<Vec<_>>(),
+            deserialized.after(&start).take(5).collect::<Vec<_>>()
+        );
+
+        // A schedule combined with a time zone serializes with the zone as a prefix.
+        let rebuilt = ZonedSchedule::new(schedule.schedule().clone(), Tz::Asia__Kolkata);
+        assert_tokens(
+            &rebuilt,
+            &[Token::String("CRON_TZ=Asia/Kolkata 0 0 9 * * Mon-Fri")],
+        );
+        assert_eq!(schedule.schedule(), rebuilt.schedule());
+
+        assert_de_tokens_error::<ZonedSchedule>(
+            &[Token::Str("CRON_TZ=Mars/Olympus_Mons 0 0 9 * * *")],
+            "Invalid time zone: Mars/Olympus_Mons",
+        );
+    }
+
+    #[test]
+    fn test_excluding_schedule_blackouts() {
+        let every_five_minutes = Schedule::from_str("0 0/5 * * * ?").unwrap();
+        let maintenance = Schedule::from_str("* * 2-3 * * ?").unwrap();
+        let schedule = ExcludingSchedule::new(every_five_minutes).with_exclusion(maintenance);
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 1, 50, 0).unwrap();
+        let fires = schedule
+            .after(&start)
+            .take(3)
+            .map(|fire| fire.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "2024-01-01T01:55:00+00:00",
+                "2024-01-01T04:00:00+00:00",
+                "2024-01-01T04:05:00+00:00"
+            ],
+            fires
+        );
+        let four = Utc.with_ymd_and_hms(2024, 1, 1, 4, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 1, 1, 1, 55, 0).single(),
+            schedule.before(&four).next()
+        );
+        assert!(schedule.includes(four));
+        assert!(!schedule.includes(four - Duration::minutes(5)));
+
+        let daily = Schedule::from_str("0 0 0 * * ?").unwrap();
+        let christmas = Schedule::from_str("0 0 0 25 12 ?").unwrap();
+        let schedule = ExcludingSchedule::new(daily).with_exclusion(christmas);
+        let christmas_eve = Utc.with_ymd_and_hms(2024, 12, 24, 0, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 12, 26, 0, 0, 0).single(),
+            schedule.next_after(&christmas_eve)
+        );
+        assert_eq!(
+            Some(christmas_eve),
+            schedule.prev_before(&(christmas_eve + Duration::days(2)))
+        );
+
+        // Intervals are instants, whatever the time zone of the DateTimes.
+        let hourly = Schedule::from_str("0 0 * * * ?").unwrap();
+        let outage_start = Utc.with_ymd_and_hms(2024, 6, 1, 10, 0, 0).unwrap();
+        let schedule = ExcludingSchedule::new(hourly)
+            .with_excluded_interval(outage_start, outage_start + Duration::hours(3));
+        let paris = Tz::Europe__Paris;
+        let before_outage = paris.with_ymd_and_hms(2024, 6, 1, 11, 30, 0).unwrap();
+        let fires = schedule
+            .after(&before_outage)
+            .take(2)
+            .map(|fire| fire.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec!["2024-06-01T15:00:00+02:00", "2024-06-01T16:00:00+02:00"],
+            fires
+        );
+        assert_eq!(
+            "2024-06-01T11:00:00+02:00",
+            schedule
+                .before(&paris.with_ymd_and_hms(2024, 6, 1, 15, 0, 0).unwrap())
+                .next()
+                .unwrap()
+                .to_rfc3339()
+        );
+    }
+
+    #[test]
+    fn test_excluding_schedule_skips_whole_exclusion_runs() {
+        let every_second = Schedule::from_str("* * * * * ?").unwrap();
+        let first_half = Schedule::from_str("* * * * 1-6 ?").unwrap();
+        let schedule = ExcludingSchedule::new(every_second.clone()).with_exclusion(first_half);
+        let new_year = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let july = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        assert_eq!(Some(july), schedule.next_after(&new_year));
+        assert_eq!(
+            Some(new_year - Duration::seconds(1)),
+            schedule.prev_before(&july)
+        );
+
+        // Runs through a DST transition are skipped in wall clock time.
+        let every_minute = Schedule::from_str("0 * * * * ?").unwrap();
+        let night = Schedule::from_str("* * 1-2 * * ?").unwrap();
+        let schedule = ExcludingSchedule::new(every_minute).with_exclusion(night);
+        let new_york = Tz::America__New_York;
+        for day in [10, 3] {
+            let month = if day == 10 { 3 } else { 11 };
+            let before = new_york
+                .with_ymd_and_hms(2024, month, day, 0, 59, 0)
+                .unwrap();
+            let after = new_york
+                .with_ymd_and_hms(2024, month, day, 3, 0, 0)
+                .unwrap();
+            assert_eq!(Some(after), schedule.next_after(&before));
+            assert_eq!(Some(before), schedule.prev_before(&after));
+        }
+    }
+
+    #[test]
+    fn test_excluding_schedule_counts_match_iteration() {
+        let base = Schedule::from_str("0 0/10 * * * ?").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 4, 15, 0, 0, 0).unwrap();
+        let schedules = [
+            ExcludingSchedule::new(base.clone()),
+            ExcludingSchedule::new(base.clone())
+                .with_exclusion(Schedule::from_str("* * 2-3 * * ?").unwrap())
+                .with_exclusion(Schedule::from_str("0 0/30 * ? * Sat,Sun").unwrap())
+                .with_exclusion(Schedule::from_str("* * 3-5 1 * ?").unwrap()),
+            ExcludingSchedule::new(base.clone())
+                .with_exclusion(Schedule::from_str("* * * ? * Mon").unwrap())
+                .with_excluded_interval(start - Duration::days(1), start + Duration::days(3))
+                .with_excluded_interval(start + Duration::days(10), start + Duration::days(12))
+                .with_excluded_interval(start + Duration::days(11), start + Duration::days(13)),
+            // A DST policy can't be combined field by field, so its matches are checked one at a
+            // time.
+            ExcludingSchedule::new(base).with_exclusion(
+                Schedule::from_str("0 * 1 * * ?")
+                    .unwrap()
+                    .with_dst_fold_policy(DstFoldPolicy::EarlierOnly),
+            ),
+        ];
+        let new_york = Tz::America__New_York;
+        for schedule in &schedules {
+            let (start, end) = (start.with_timezone(&new_york), end.with_timezone(&new_york));
+            let forward = schedule.between(start..end).collect::<Vec<_>>();
+            let mut backward = schedule.between(start..end).rev().collect::<Vec<_>>();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            assert_eq!(forward.len() as u64, schedule.count_between(start..end));
+            assert_eq!(
+                forward.len() as u64 - 1,
+                schedule.count_between((Excluded(forward[0]), Excluded(end)))
+            );
+            assert!(forward.iter().all(|fire| schedule.includes(*fire)));
+            assert_eq!(
+                forward[..5].to_vec(),
+                schedule
+                    .after(&(start - Duration::nanoseconds(1)))
+                    .take(5)
+                    .collect::<Vec<_>>()
+            );
+        }
+        let counts = schedules
+            .iter()
+            .map(|schedule| schedule.count_between(start..end))
+            .collect::<Vec<_>>();
+        assert!(counts[0] > counts[1] && counts[0] > counts[2] && counts[0] > counts[3]);
+    }
+
+    #[test]
+    fn test_schedule_set_merges_across_dst_fold() {
+        let set: ScheduleSet = ["0 30 1 * * ?", "0 0/30 1 * * ?", "0 0 3 * * ?"]
+            .iter()
+            .map(|expression| Schedule::from_str(expression).unwrap())
+            .collect();
+        let new_york = Tz::America__New_York;
+        let start = new_york.with_ymd_and_hms(2024, 11, 3, 0, 0, 0).unwrap();
+        let fires = set
+            .after(&start)
+            .take(6)
+            .map(|fire| fire.to_rfc3339())
+            .collect::<Vec<_>>();
+        // Both passes through 01:00 and 01:30 fire, each once.
+        assert_eq!(
+            vec![
+                "2024-11-03T01:00:00-04:00",
+                "2024-11-03T01:30:00-04:00",
+                "2024-11-03T01:00:00-05:00",
+                "2024-11-03T01:30:00-05:00",
+                "2024-11-03T03:00:00-05:00",
+                "2024-11-04T01:00:00-05:00",
+            ],
+            fires
+        );
+        let after_fires = set
+            .between(start..new_york.with_ymd_and_hms(2024, 11, 4, 1, 0, 0).unwrap())
+            .collect::<Vec<_>>();
+        let mut before_fires = set
+            .before(&new_york.with_ymd_and_hms(2024, 11, 4, 1, 0, 0).unwrap())
+            .take_while(|fire| *fire >= start)
+            .collect::<Vec<_>>();
+        before_fires.reverse();
+        assert_eq!(after_fires, before_fires);
+        assert_eq!(5, after_fires.len());
+        assert!(after_fires.iter().all(|fire| set.includes(*fire)));
+    }
+
     struct CronIterationTestCase {
         name: &'static str,
         timezone: Tz,